@@ -62,6 +62,27 @@ impl XpcControlService {
             Request::SnapshotList(_) => self.handle_snapshot_list(SnapshotListRequest {}).await,
             Request::BranchCreate((_, req)) => self.handle_branch_create(req).await,
             Request::BranchBind((_, req)) => self.handle_branch_bind(req).await,
+            Request::VersionHandshake(handshake) => {
+                self.handle_version_handshake(handshake).await
+            }
+        }
+    }
+
+    /// Negotiate a protocol version from the client's advertised set and echo
+    /// back the one this host chose.
+    async fn handle_version_handshake(
+        &self,
+        handshake: VersionHandshakeRequest,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        match negotiate_version(&handshake.supported_versions) {
+            Ok(chosen) => {
+                let response = Response::version_handshake(String::from_utf8_lossy(&chosen).to_string());
+                Ok(response.as_ssz_bytes())
+            }
+            Err(e) => {
+                let response = Response::error(format!("{}", e), Some(22)); // EINVAL
+                Ok(response.as_ssz_bytes())
+            }
         }
     }
 
@@ -227,6 +227,18 @@ impl AgentFsFuse {
                     }
                 }
             }
+            Request::VersionHandshake(handshake) => match negotiate_version(&handshake.supported_versions) {
+                Ok(chosen) => {
+                    let response =
+                        Response::version_handshake(String::from_utf8_lossy(&chosen).to_string());
+                    response.as_ssz_bytes().map_err(|_| EIO)
+                }
+                Err(e) => {
+                    error!("Version negotiation failed: {}", e);
+                    let response = Response::error(format!("{}", e), Some(EINVAL as u32));
+                    response.as_ssz_bytes().map_err(|_| EIO)
+                }
+            },
         }
     }
 }
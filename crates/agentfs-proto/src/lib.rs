@@ -41,5 +41,7 @@ pub use messages::{
     SnapshotInfo,
     SnapshotListRequest,
     SnapshotListResponse,
+    VersionHandshakeRequest,
+    VersionHandshakeResponse,
 };
 pub use validation::*;
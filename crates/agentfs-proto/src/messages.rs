@@ -14,6 +14,11 @@ pub enum Request {
     SnapshotList(Vec<u8>),                            // version
     BranchCreate((Vec<u8>, BranchCreateRequest)),     // (version, request)
     BranchBind((Vec<u8>, BranchBindRequest)),         // (version, request)
+    // Versionless by design: a client sends this before it knows which
+    // schema version the server supports, so it can't carry a version tag
+    // itself. Appended last to keep the union's existing selector indices
+    // stable for older peers.
+    VersionHandshake(VersionHandshakeRequest),
 }
 
 /// Response union - operation-specific success responses or errors
@@ -25,6 +30,7 @@ pub enum Response {
     BranchCreate(BranchCreateResponse),
     BranchBind(BranchBindResponse),
     Error(ErrorResponse),
+    VersionHandshake(VersionHandshakeResponse),
 }
 
 /// Error response
@@ -34,6 +40,25 @@ pub struct ErrorResponse {
     pub code: Option<u32>,
 }
 
+/// Version negotiation handshake request.
+///
+/// A client sends this first, advertising every schema version it knows how
+/// to speak; the server picks the newest one they have in common (see
+/// [`crate::validation::negotiate_version`]) and echoes it back in
+/// [`VersionHandshakeResponse`]. Every other request in this union then
+/// carries that chosen version explicitly.
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub struct VersionHandshakeRequest {
+    pub supported_versions: Vec<Vec<u8>>,
+}
+
+/// Version negotiation handshake response, carrying the version the server
+/// chose from the client's advertised set.
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub struct VersionHandshakeResponse {
+    pub chosen_version: Vec<u8>,
+}
+
 /// Snapshot creation request payload
 #[derive(Clone, Debug, PartialEq, Encode, Decode)]
 pub struct SnapshotCreateRequest {
@@ -274,6 +299,12 @@ impl Request {
             },
         ))
     }
+
+    pub fn version_handshake(supported_versions: Vec<String>) -> Self {
+        Self::VersionHandshake(VersionHandshakeRequest {
+            supported_versions: supported_versions.into_iter().map(String::into_bytes).collect(),
+        })
+    }
 }
 
 impl Response {
@@ -299,6 +330,12 @@ impl Response {
             code,
         })
     }
+
+    pub fn version_handshake(chosen_version: String) -> Self {
+        Self::VersionHandshake(VersionHandshakeResponse {
+            chosen_version: chosen_version.into_bytes(),
+        })
+    }
 }
 
 // Constructors for filesystem operation SSZ union variants
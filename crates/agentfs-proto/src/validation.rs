@@ -1,8 +1,23 @@
 //! Schema validation for AgentFS control messages
+//!
+//! Requests (other than the version handshake itself) carry an explicit
+//! version tag, mirroring how management APIs carry an explicit major
+//! version. That lets the control protocol add fields and new request kinds
+//! under a new version while this crate keeps validating and decoding older
+//! versions for peers that haven't upgraded yet.
 
 use crate::messages::*;
 use thiserror::Error;
 
+/// Every wire-protocol version this build knows how to validate and decode,
+/// oldest first. Add a new entry (and a matching `validate_request_v*`
+/// function) when introducing a schema revision; keep the old entries so
+/// peers that negotiate down to them keep working.
+pub const SUPPORTED_VERSIONS: &[&[u8]] = &[b"1"];
+
+/// The newest version this build offers during negotiation.
+pub const CURRENT_VERSION: &[u8] = SUPPORTED_VERSIONS[SUPPORTED_VERSIONS.len() - 1];
+
 /// Validation error
 #[derive(Error, Debug)]
 pub enum ValidationError {
@@ -10,33 +25,92 @@ pub enum ValidationError {
     Schema(String),
     #[error("SSZ decoding failed: {0}")]
     SszDecode(String),
+    #[error("unsupported protocol version '{requested}' (supported: {})", .supported.join(", "))]
+    UnsupportedVersion {
+        requested: String,
+        supported: Vec<String>,
+    },
+}
+
+fn supported_versions_display() -> Vec<String> {
+    SUPPORTED_VERSIONS.iter().map(|v| String::from_utf8_lossy(v).into_owned()).collect()
+}
+
+fn check_version(version: &[u8]) -> Result<(), ValidationError> {
+    if SUPPORTED_VERSIONS.iter().any(|supported| *supported == version) {
+        Ok(())
+    } else {
+        Err(ValidationError::UnsupportedVersion {
+            requested: String::from_utf8_lossy(version).into_owned(),
+            supported: supported_versions_display(),
+        })
+    }
+}
+
+/// Negotiate a protocol version from a client's advertised supported versions.
+///
+/// Picks the newest version both this build and the client support. Returns
+/// a structured [`ValidationError::UnsupportedVersion`] listing what this
+/// build does support when there's no overlap, so callers can surface a
+/// clear incompatibility message instead of a generic decode failure further
+/// down the line.
+pub fn negotiate_version(client_supported: &[Vec<u8>]) -> Result<Vec<u8>, ValidationError> {
+    SUPPORTED_VERSIONS
+        .iter()
+        .rev() // newest first
+        .find(|server_version| client_supported.iter().any(|v| v.as_slice() == **server_version))
+        .map(|version| version.to_vec())
+        .ok_or_else(|| ValidationError::UnsupportedVersion {
+            requested: client_supported
+                .iter()
+                .map(|v| String::from_utf8_lossy(v).into_owned())
+                .collect::<Vec<_>>()
+                .join(", "),
+            supported: supported_versions_display(),
+        })
 }
 
-/// Validate a decoded request against its logical schema
+/// Validate a decoded request against its logical schema.
+///
+/// The version handshake is validated on its own (it precedes negotiation,
+/// so it has no version tag to check); every other request dispatches to the
+/// per-version validator matching its advertised version.
 pub fn validate_request(request: &Request) -> Result<(), ValidationError> {
     match request {
-        Request::SnapshotCreate((version, _))
-        | Request::BranchCreate((version, _))
-        | Request::BranchBind((version, _)) => {
-            if version != b"1" {
-                return Err(ValidationError::Schema("version must be '1'".to_string()));
+        Request::VersionHandshake(handshake) => {
+            if handshake.supported_versions.is_empty() {
+                return Err(ValidationError::Schema(
+                    "version handshake must advertise at least one supported version".to_string(),
+                ));
             }
             Ok(())
         }
-        Request::SnapshotList(version) => {
-            if version != b"1" {
-                return Err(ValidationError::Schema("version must be '1'".to_string()));
+        Request::SnapshotCreate((version, _))
+        | Request::BranchCreate((version, _))
+        | Request::BranchBind((version, _))
+        | Request::SnapshotList(version) => {
+            check_version(version)?;
+            match version.as_slice() {
+                b"1" => validate_request_v1(request),
+                _ => unreachable!("check_version already rejected unknown versions"),
             }
-            Ok(())
         }
     }
 }
 
+/// Version-1 structural checks. Split out from the dispatcher above so a
+/// future `validate_request_v2` can diverge (e.g. requiring fields v1 left
+/// optional) without duplicating the version check that routes to it.
+fn validate_request_v1(_request: &Request) -> Result<(), ValidationError> {
+    Ok(())
+}
+
 /// Validate a decoded response against its logical schema
 pub fn validate_response(response: &Response) -> Result<(), ValidationError> {
     // For union responses, the structure is validated by the SSZ decoding itself
     // Error responses are always valid, success responses have their structure enforced by the union
     match response {
+        Response::VersionHandshake(handshake) => check_version(&handshake.chosen_version),
         Response::SnapshotCreate(_)
         | Response::SnapshotList(_)
         | Response::BranchCreate(_)
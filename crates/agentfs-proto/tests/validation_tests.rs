@@ -68,3 +68,51 @@ fn test_valid_error_response() {
 
     assert!(validate_response(&response).is_ok());
 }
+
+#[test]
+fn test_negotiate_version_picks_common_version() {
+    let chosen = negotiate_version(&[b"0".to_vec(), b"1".to_vec()]);
+
+    assert_eq!(chosen.unwrap(), b"1".to_vec());
+}
+
+#[test]
+fn test_negotiate_version_no_overlap() {
+    let result = negotiate_version(&[b"0".to_vec()]);
+
+    assert!(matches!(
+        result,
+        Err(ValidationError::UnsupportedVersion { .. })
+    ));
+}
+
+#[test]
+fn test_valid_version_handshake_request() {
+    let request = Request::version_handshake(vec!["1".to_string()]);
+
+    assert!(validate_request(&request).is_ok());
+}
+
+#[test]
+fn test_version_handshake_request_requires_at_least_one_version() {
+    let request = Request::version_handshake(vec![]);
+
+    assert!(validate_request(&request).is_err());
+}
+
+#[test]
+fn test_valid_version_handshake_response() {
+    let response = Response::version_handshake("1".to_string());
+
+    assert!(validate_response(&response).is_ok());
+}
+
+#[test]
+fn test_version_handshake_response_rejects_unsupported_version() {
+    let response = Response::version_handshake("99".to_string());
+
+    assert!(matches!(
+        validate_response(&response),
+        Err(ValidationError::UnsupportedVersion { .. })
+    ));
+}
@@ -1,8 +1,23 @@
-use anyhow::{anyhow, Result};
-use ah_fs_snapshots::{provider_for, ProviderCapabilities, SnapshotProviderKind};
+use anyhow::{anyhow, Context, Result};
+use ah_core::DatabaseManager;
+use ah_fs_snapshots::{
+    all_providers, provider_for, Error as SnapshotError, FsSnapshotProvider, PreparedWorkspace,
+    ProviderCapabilities, SnapshotProviderKind, WorkingCopyMode,
+};
+use ah_local_db::FsSnapshotRecord;
+use ah_repo::VcsRepo;
+use agentfs_proto::Response;
 use clap::{Args, Subcommand};
+use nix::sys::statvfs::statvfs;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::transport::{
+    build_branch_bind_request, build_branch_create_request, build_snapshot_create_request,
+    send_control_request, ControlTransport,
+};
 
 /// JSON output for filesystem status
 #[derive(Serialize, Deserialize)]
@@ -21,6 +36,77 @@ struct FsCapabilitiesJson {
     supports_cow_overlay: bool,
 }
 
+/// JSON output for `ah agent-fs benchmark`
+#[derive(Serialize, Deserialize)]
+struct BenchmarkJson {
+    path: String,
+    samples: usize,
+    working_set_size: u64,
+    providers: Vec<ProviderBenchmarkJson>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProviderBenchmarkJson {
+    provider: String,
+    capability_score: u8,
+    samples_taken: usize,
+    snapshot_latency_ms: LatencyStatsJson,
+    branch_latency_ms: LatencyStatsJson,
+    teardown_latency_ms: LatencyStatsJson,
+    snapshots_per_second: f64,
+    /// Median extra disk space (free-space delta) consumed per snapshot, in
+    /// bytes, or `None` if free space couldn't be sampled on this filesystem.
+    extra_space_per_snapshot_bytes: Option<i64>,
+    /// Set instead of the fields above if this provider couldn't be
+    /// benchmarked on the given path (unsupported, or a provider call
+    /// failed partway through the sample loop).
+    error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct LatencyStatsJson {
+    median_ms: f64,
+    p95_ms: f64,
+}
+
+impl LatencyStatsJson {
+    fn from_durations(durations: &[Duration]) -> Self {
+        Self {
+            median_ms: percentile_ms(durations, 0.5),
+            p95_ms: percentile_ms(durations, 0.95),
+        }
+    }
+}
+
+/// Linear-interpolation-free (nearest-rank) percentile over `durations`,
+/// converted to fractional milliseconds. Good enough for a benchmark report;
+/// `durations` is sorted in place via a local copy.
+fn percentile_ms(durations: &[Duration], percentile: f64) -> f64 {
+    if durations.is_empty() {
+        return 0.0;
+    }
+    let mut sorted: Vec<Duration> = durations.to_vec();
+    sorted.sort();
+    let rank = ((sorted.len() as f64 - 1.0) * percentile).round() as usize;
+    sorted[rank.min(sorted.len() - 1)].as_secs_f64() * 1000.0
+}
+
+fn median_u64(values: &[u64]) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    Some(sorted[sorted.len() / 2])
+}
+
+/// Free space remaining on the filesystem backing `path`, used to estimate
+/// the extra space a snapshot consumes by sampling before/after each one.
+fn free_space_bytes(path: &Path) -> Option<u64> {
+    let stat = statvfs(path).ok()?;
+    Some(stat.blocks_available() as u64 * stat.fragment_size() as u64)
+}
+
 #[derive(Args)]
 pub struct StatusOptions {
     /// Path to analyze (default: current working directory)
@@ -40,6 +126,25 @@ pub struct StatusOptions {
     detect_only: bool,
 }
 
+#[derive(Args)]
+pub struct BenchmarkOptions {
+    /// Path to benchmark (default: current working directory)
+    #[arg(short, long)]
+    path: Option<PathBuf>,
+
+    /// Number of snapshot/branch/teardown samples to take per provider
+    #[arg(long, default_value_t = 20)]
+    samples: usize,
+
+    /// Size in bytes of the working-set file rewritten before each snapshot
+    #[arg(long, default_value_t = 10 * 1024 * 1024)]
+    working_set_size: u64,
+
+    /// Emit machine-readable JSON output
+    #[arg(long)]
+    json: bool,
+}
+
 #[derive(Args)]
 pub struct InitSessionOptions {
     /// Optional name for the initial snapshot
@@ -97,6 +202,10 @@ pub enum AgentFsCommands {
     /// Run filesystem detection and report capabilities
     Status(StatusOptions),
 
+    /// Measure snapshot/branch/teardown performance of each available
+    /// snapshot provider on a given path
+    Benchmark(BenchmarkOptions),
+
     /// Create initial AgentFS snapshot for a session
     InitSession(InitSessionOptions),
 
@@ -112,8 +221,9 @@ pub enum AgentFsCommands {
 
 impl AgentFsCommands {
     pub async fn run(self) -> Result<()> {
-        match self {
+        let result = match self {
             AgentFsCommands::Status(opts) => Self::status(opts).await,
+            AgentFsCommands::Benchmark(opts) => Self::benchmark(opts).await,
             AgentFsCommands::InitSession(opts) => Self::init_session(opts).await,
             AgentFsCommands::Snapshots(opts) => Self::list_snapshots(opts).await,
             AgentFsCommands::Branch { subcommand } => match subcommand {
@@ -125,6 +235,30 @@ impl AgentFsCommands {
                     Self::branch_exec(branch_id, command).await
                 }
             },
+        };
+
+        if let Err(ref err) = result {
+            Self::log_failure(err);
+        }
+
+        result
+    }
+
+    /// Best-effort record of a command failure so it can be inspected later
+    /// via `SELECT ... FROM errors`. Never itself fails the command: if the
+    /// database can't be reached we just skip logging.
+    fn log_failure(err: &anyhow::Error) {
+        let Ok(db) = ah_core::DatabaseManager::new() else {
+            return;
+        };
+
+        match err.downcast_ref::<ah_core::Error>() {
+            Some(core_error) => {
+                let _ = db.record_error(None, core_error);
+            }
+            None => {
+                let _ = db.record_error(None, &ah_core::Error::generic(err.to_string()));
+            }
         }
     }
 
@@ -210,6 +344,187 @@ impl AgentFsCommands {
         Ok(())
     }
 
+    async fn benchmark(opts: BenchmarkOptions) -> Result<()> {
+        let path = opts.path.unwrap_or_else(|| std::env::current_dir().unwrap());
+        let samples = opts.samples.max(1);
+
+        let providers: Vec<ProviderBenchmarkJson> = all_providers()
+            .into_iter()
+            .map(|provider| {
+                let capabilities = provider.detect_capabilities(&path);
+                Self::benchmark_provider(
+                    provider.as_ref(),
+                    &capabilities,
+                    &path,
+                    samples,
+                    opts.working_set_size,
+                )
+            })
+            .collect();
+
+        let json = BenchmarkJson {
+            path: path.display().to_string(),
+            samples,
+            working_set_size: opts.working_set_size,
+            providers,
+        };
+
+        if opts.json {
+            println!("{}", serde_json::to_string_pretty(&json)?);
+        } else {
+            println!("Snapshot provider benchmark for: {}", json.path);
+            println!("Samples per provider: {}, working-set size: {} bytes", json.samples, json.working_set_size);
+            for provider in &json.providers {
+                println!("\n{} (capability score {})", provider.provider, provider.capability_score);
+                match &provider.error {
+                    Some(err) => println!("  skipped: {}", err),
+                    None => {
+                        println!("  snapshots/sec: {:.2}", provider.snapshots_per_second);
+                        println!(
+                            "  snapshot latency: median {:.2}ms, p95 {:.2}ms",
+                            provider.snapshot_latency_ms.median_ms, provider.snapshot_latency_ms.p95_ms
+                        );
+                        println!(
+                            "  branch latency:   median {:.2}ms, p95 {:.2}ms",
+                            provider.branch_latency_ms.median_ms, provider.branch_latency_ms.p95_ms
+                        );
+                        println!(
+                            "  teardown latency: median {:.2}ms, p95 {:.2}ms",
+                            provider.teardown_latency_ms.median_ms, provider.teardown_latency_ms.p95_ms
+                        );
+                        match provider.extra_space_per_snapshot_bytes {
+                            Some(space) => println!("  extra space/snapshot: {} bytes", space),
+                            None => println!("  extra space/snapshot: unknown"),
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Exercise `snapshot_now`/`branch_from_snapshot`/teardown `samples` times
+    /// against `provider` at `path`, rewriting a `working_set_size`-byte file
+    /// before each snapshot so there's something new to snapshot.
+    fn benchmark_provider(
+        provider: &dyn FsSnapshotProvider,
+        capabilities: &ProviderCapabilities,
+        path: &Path,
+        samples: usize,
+        working_set_size: u64,
+    ) -> ProviderBenchmarkJson {
+        let provider_name = format!("{:?}", capabilities.kind);
+        let skipped = |error: String| ProviderBenchmarkJson {
+            provider: provider_name.clone(),
+            capability_score: capabilities.score,
+            samples_taken: 0,
+            snapshot_latency_ms: LatencyStatsJson::default(),
+            branch_latency_ms: LatencyStatsJson::default(),
+            teardown_latency_ms: LatencyStatsJson::default(),
+            snapshots_per_second: 0.0,
+            extra_space_per_snapshot_bytes: None,
+            error: Some(error),
+        };
+
+        if capabilities.score == 0 {
+            return skipped("provider is not usable on this path (capability score 0)".to_string());
+        }
+
+        let workspace: PreparedWorkspace =
+            match provider.prepare_writable_workspace(path, WorkingCopyMode::Auto) {
+                Ok(workspace) => workspace,
+                Err(e) => return skipped(format!("failed to prepare workspace: {}", e)),
+            };
+
+        let mut snapshot_latencies = Vec::with_capacity(samples);
+        let mut branch_latencies = Vec::with_capacity(samples);
+        let mut teardown_latencies = Vec::with_capacity(samples);
+        let mut space_deltas = Vec::with_capacity(samples);
+
+        for i in 0..samples {
+            if let Err(e) = Self::rewrite_working_set(&workspace.exec_path, working_set_size, i) {
+                let _ = provider.cleanup(&workspace.cleanup_token);
+                return skipped(format!("failed to write working set: {}", e));
+            }
+
+            let free_before = free_space_bytes(&workspace.exec_path);
+
+            let snapshot_start = Instant::now();
+            let snapshot = match provider.snapshot_now(&workspace, Some(&format!("bench-{i}"))) {
+                Ok(snapshot) => snapshot,
+                Err(e) => {
+                    let _ = provider.cleanup(&workspace.cleanup_token);
+                    return skipped(format!("snapshot_now failed on sample {}: {}", i, e));
+                }
+            };
+            snapshot_latencies.push(snapshot_start.elapsed());
+
+            if let (Some(before), Some(after)) =
+                (free_before, free_space_bytes(&workspace.exec_path))
+            {
+                space_deltas.push(before.saturating_sub(after));
+            }
+
+            let branch_start = Instant::now();
+            let branch = match provider.branch_from_snapshot(&snapshot, WorkingCopyMode::Auto) {
+                Ok(branch) => branch,
+                Err(e) => {
+                    let _ = provider.cleanup(&workspace.cleanup_token);
+                    return skipped(format!("branch_from_snapshot failed on sample {}: {}", i, e));
+                }
+            };
+            branch_latencies.push(branch_start.elapsed());
+
+            let teardown_start = Instant::now();
+            if let Err(e) = provider.cleanup(&branch.cleanup_token) {
+                let _ = provider.cleanup(&workspace.cleanup_token);
+                return skipped(format!("branch teardown failed on sample {}: {}", i, e));
+            }
+            teardown_latencies.push(teardown_start.elapsed());
+        }
+
+        let _ = provider.cleanup(&workspace.cleanup_token);
+
+        let total_snapshot_time: Duration = snapshot_latencies.iter().sum();
+        let snapshots_per_second = if total_snapshot_time.as_secs_f64() > 0.0 {
+            samples as f64 / total_snapshot_time.as_secs_f64()
+        } else {
+            0.0
+        };
+
+        ProviderBenchmarkJson {
+            provider: provider_name,
+            capability_score: capabilities.score,
+            samples_taken: samples,
+            snapshot_latency_ms: LatencyStatsJson::from_durations(&snapshot_latencies),
+            branch_latency_ms: LatencyStatsJson::from_durations(&branch_latencies),
+            teardown_latency_ms: LatencyStatsJson::from_durations(&teardown_latencies),
+            snapshots_per_second,
+            extra_space_per_snapshot_bytes: median_u64(&space_deltas).map(|bytes| bytes as i64),
+            error: None,
+        }
+    }
+
+    /// Overwrite the benchmark working-set file with `size` bytes so each
+    /// sample snapshots a genuinely changed tree; `seed` varies the content
+    /// so CoW providers can't dedupe consecutive samples down to nothing.
+    fn rewrite_working_set(workspace_path: &Path, size: u64, seed: usize) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let file_path = workspace_path.join("agent-fs-benchmark-working-set.bin");
+        let mut file = std::fs::File::create(file_path)?;
+        let pattern = (seed % 256) as u8;
+        let chunk = vec![pattern; 64 * 1024];
+        let mut remaining = size;
+        while remaining > 0 {
+            let n = remaining.min(chunk.len() as u64) as usize;
+            file.write_all(&chunk[..n])?;
+            remaining -= n as u64;
+        }
+        Ok(())
+    }
+
     fn detect_filesystem_type(path: &PathBuf) -> String {
         // Simple filesystem type detection using /proc/mounts or similar
         // For now, return a placeholder
@@ -223,96 +538,240 @@ impl AgentFsCommands {
     }
 
     async fn init_session(opts: InitSessionOptions) -> Result<()> {
-        // TODO: Once AgentFS and database persistence are implemented, this will:
-        // 1. Resolve repository path (default to current dir)
-        // 2. Detect appropriate snapshot provider for the path
-        // 3. Prepare writable workspace if needed
-        // 4. Create initial snapshot using provider.snapshot_now()
-        // 5. Record snapshot metadata in database
-
         let repo_path = opts.repo.unwrap_or_else(|| std::env::current_dir().unwrap());
+        let repo = VcsRepo::new(&repo_path)
+            .map_err(|e| anyhow!("Failed to resolve repository at {}: {}", repo_path.display(), e))?;
 
         println!(
             "Initializing session snapshots for repository: {}",
             repo_path.display()
         );
-        if let Some(name) = &opts.name {
-            println!("Snapshot name: {}", name);
-        }
+
+        let transport = ControlTransport::new(repo_path.clone())
+            .map_err(|e| SnapshotError::provider(format!("AgentFS not mounted at {}: {}", repo_path.display(), e)))?;
+        let request = build_snapshot_create_request(opts.name.clone());
+        let response = send_control_request(transport, request)
+            .await
+            .map_err(|e| SnapshotError::provider(format!("AgentFS control request failed: {}", e)))?;
+
+        let snapshot = match response {
+            Response::SnapshotCreate(resp) => resp.snapshot,
+            Response::Error(err) => {
+                return Err(
+                    SnapshotError::snapshot_creation(String::from_utf8_lossy(&err.error).to_string()).into(),
+                );
+            }
+            other => return Err(SnapshotError::provider(format!("unexpected AgentFS response: {:?}", other)).into()),
+        };
+        let snapshot_id = String::from_utf8(snapshot.id)
+            .map_err(|e| SnapshotError::provider(format!("invalid snapshot id: {}", e)))?;
+
+        let db = DatabaseManager::new().context("Failed to initialize database")?;
+        let repo_id = db.get_or_create_repo(&repo).context("Failed to get or create repository record")?;
+        let agent_id =
+            db.get_or_create_agent("agentfs", "1").context("Failed to get or create agent record")?;
+        let runtime_id = db.get_or_create_local_runtime().context("Failed to get or create runtime record")?;
+
+        let session_id = DatabaseManager::generate_session_id();
+        let session_record = ah_local_db::SessionRecord {
+            id: session_id.clone(),
+            repo_id: Some(repo_id),
+            workspace_id: None,
+            agent_id: Some(agent_id),
+            runtime_id: Some(runtime_id),
+            multiplexer_kind: None,
+            mux_session: None,
+            mux_window: None,
+            pane_left: None,
+            pane_right: None,
+            pid_agent: None,
+            status: "created".to_string(),
+            log_path: None,
+            workspace_path: Some(repo_path.display().to_string()),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            ended_at: None,
+        };
+        db.create_session(&session_record).context("Failed to create session record")?;
+
+        let snapshot_record = FsSnapshotRecord {
+            id: 0, // set by autoincrement
+            session_id: session_id.clone(),
+            ts: chrono::Utc::now().to_rfc3339(),
+            provider: "agentfs".to_string(),
+            ref_: Some(snapshot_id.clone()),
+            path: Some(repo_path.display().to_string()),
+            parent_id: None,
+            metadata: snapshot.name.map(|n| String::from_utf8_lossy(&n).to_string()),
+        };
+        db.create_fs_snapshot(&snapshot_record).context("Failed to record filesystem snapshot")?;
+
+        println!("Created session '{}' with initial snapshot '{}'", session_id, snapshot_id);
         if let Some(workspace) = &opts.workspace {
             println!("Workspace: {}", workspace);
         }
-        println!("Note: AgentFS and database persistence not yet implemented in this milestone");
-        println!("When implemented, this will create initial filesystem snapshots for time travel");
 
         Ok(())
     }
 
     async fn list_snapshots(opts: SnapshotsOptions) -> Result<()> {
-        // TODO: Once database persistence is implemented, this will:
-        // 1. Parse session_id (branch name or repo/branch)
-        // 2. Query fs_snapshots table to find snapshots for the session
-        // 3. Display formatted list of snapshots with metadata
+        let db = DatabaseManager::new().context("Failed to initialize database")?;
+
+        let snapshots =
+            db.list_fs_snapshots(&opts.session_id).context("Failed to query filesystem snapshots")?;
+
+        if snapshots.is_empty() {
+            println!("No snapshots recorded for session '{}'", opts.session_id);
+            return Ok(());
+        }
 
         println!("Snapshots for session '{}':", opts.session_id);
-        println!("Note: Database persistence not yet implemented in this milestone");
-        println!("When implemented, this will show:");
-        println!("- Snapshot ID");
-        println!("- Timestamp");
-        println!("- Provider type");
-        println!("- Reference/path");
-        println!("- Optional labels and metadata");
-
-        // For now, show that the command structure is ready
-        println!(
-            "\nCommand parsing successful for session: {}",
-            opts.session_id
-        );
+        Self::print_snapshot_tree(&snapshots, None, 0);
 
         Ok(())
     }
 
+    /// Recursively print snapshots whose `parent_id` matches `parent`,
+    /// indenting children under their parent to show the branch tree.
+    fn print_snapshot_tree(snapshots: &[FsSnapshotRecord], parent: Option<i64>, depth: usize) {
+        for snapshot in snapshots.iter().filter(|s| s.parent_id == parent) {
+            println!(
+                "{}- {} [{}] {} ({})",
+                "  ".repeat(depth),
+                snapshot.ref_.as_deref().unwrap_or("<unknown>"),
+                snapshot.provider,
+                snapshot.metadata.as_deref().unwrap_or(""),
+                snapshot.ts,
+            );
+            Self::print_snapshot_tree(snapshots, Some(snapshot.id), depth + 1);
+        }
+    }
+
     async fn branch_create(snapshot_id: String, name: Option<String>) -> Result<()> {
-        // TODO: Once AgentFS integration is implemented, this will:
-        // 1. Validate snapshot_id exists
-        // 2. Get the provider for the snapshot
-        // 3. Call provider.branch_from_snapshot() to create writable branch
-        // 4. Record branch metadata in database
+        let mount_point = std::env::current_dir().context("Failed to resolve current directory")?;
 
         println!("Creating branch from snapshot '{}'", snapshot_id);
+        let transport = ControlTransport::new(mount_point)
+            .map_err(|e| SnapshotError::provider(format!("AgentFS not mounted: {}", e)))?;
+        let request = build_branch_create_request(snapshot_id, name.clone());
+        let response = send_control_request(transport, request)
+            .await
+            .map_err(|e| SnapshotError::provider(format!("AgentFS control request failed: {}", e)))?;
+
+        let branch = match response {
+            Response::BranchCreate(resp) => resp.branch,
+            Response::Error(err) => {
+                return Err(
+                    SnapshotError::provider(String::from_utf8_lossy(&err.error).to_string()).into(),
+                );
+            }
+            other => return Err(SnapshotError::provider(format!("unexpected AgentFS response: {:?}", other)).into()),
+        };
+        let branch_id = String::from_utf8(branch.id)
+            .map_err(|e| SnapshotError::provider(format!("invalid branch id: {}", e)))?;
+
+        println!("Created branch '{}'", branch_id);
         if let Some(name) = &name {
             println!("Branch name: {}", name);
         }
-        println!("Note: AgentFS integration not yet implemented in this milestone");
-        println!("When implemented, this will create a writable branch for time travel");
 
         Ok(())
     }
 
     async fn branch_bind(branch_id: String) -> Result<()> {
-        // TODO: Once AgentFS integration is implemented, this will:
-        // 1. Validate branch_id exists
-        // 2. Bind the current process to the branch view
-        // 3. Set up the filesystem overlay for the process
+        let mount_point = std::env::current_dir().context("Failed to resolve current directory")?;
 
         println!("Binding to branch '{}'", branch_id);
-        println!("Note: AgentFS process binding not yet implemented in this milestone");
-        println!("When implemented, this will make the branch view available to child processes");
-
-        Ok(())
+        let transport = ControlTransport::new(mount_point)
+            .map_err(|e| SnapshotError::provider(format!("AgentFS not mounted: {}", e)))?;
+        let request = build_branch_bind_request(branch_id.clone(), Some(std::process::id()));
+        let response = send_control_request(transport, request)
+            .await
+            .map_err(|e| SnapshotError::provider(format!("AgentFS control request failed: {}", e)))?;
+
+        match response {
+            Response::BranchBind(resp) => {
+                println!("Bound process {} to branch '{}'", resp.pid, branch_id);
+                Ok(())
+            }
+            Response::Error(err) => {
+                Err(SnapshotError::provider(String::from_utf8_lossy(&err.error).to_string()).into())
+            }
+            other => Err(SnapshotError::provider(format!("unexpected AgentFS response: {:?}", other)).into()),
+        }
     }
 
     async fn branch_exec(branch_id: String, command: Vec<String>) -> Result<()> {
-        // TODO: Once AgentFS integration is implemented, this will:
-        // 1. Bind to the specified branch
-        // 2. Execute the command in that branch context
-        // 3. Return the command's exit status
+        if command.is_empty() {
+            anyhow::bail!("No command specified");
+        }
+
+        let mount_point = std::env::current_dir().context("Failed to resolve current directory")?;
 
         println!("Executing command in branch '{}' context", branch_id);
         println!("Command: {:?}", command);
-        println!("Note: AgentFS branch execution not yet implemented in this milestone");
-        println!("When implemented, this will run the command with the branch filesystem view");
+
+        let mut cmd = tokio::process::Command::new(&command[0]);
+        cmd.args(&command[1..]);
+        // Stop the child right before it execs the target binary, so the
+        // branch bind below lands before the process can do any filesystem
+        // I/O. The fork-but-not-yet-exec'd child still runs this crate's own
+        // binary, so raising SIGSTOP on itself here is safe.
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::raise(libc::SIGSTOP) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        let mut child = cmd.spawn()?;
+        let pid = child.id().unwrap_or(0);
+        wait_until_stopped(pid).await?;
+
+        let transport = ControlTransport::new(mount_point)
+            .map_err(|e| SnapshotError::provider(format!("AgentFS not mounted: {}", e)))?;
+        let request = build_branch_bind_request(branch_id.clone(), Some(pid));
+        let response = send_control_request(transport, request)
+            .await
+            .map_err(|e| SnapshotError::provider(format!("AgentFS control request failed: {}", e)))?;
+
+        if let Response::Error(err) = response {
+            let _ = child.kill().await;
+            return Err(SnapshotError::provider(String::from_utf8_lossy(&err.error).to_string()).into());
+        }
+
+        // Now that the process is bound to the branch view, let it proceed
+        // to exec the target command.
+        if unsafe { libc::kill(pid as i32, libc::SIGCONT) } != 0 {
+            let _ = child.kill().await;
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        let status = child.wait().await.context("Failed to wait for branch-bound process")?;
+        if !status.success() {
+            anyhow::bail!("Command exited with status {}", status);
+        }
 
         Ok(())
     }
 }
+
+/// Poll `/proc/<pid>/stat` until the process is in stopped state (`T`),
+/// i.e. has hit the `SIGSTOP` raised by its own `pre_exec` hook just before
+/// the real `execvp`.
+async fn wait_until_stopped(pid: u32) -> Result<()> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let stat = tokio::fs::read_to_string(format!("/proc/{}/stat", pid))
+            .await
+            .context("Failed to read branch-bound process state")?;
+        let state = stat.rsplit_once(')').and_then(|(_, rest)| rest.split_whitespace().next());
+        if state == Some("T") {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            anyhow::bail!("Timed out waiting for branch-bound process to stop before exec");
+        }
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+}
@@ -1,8 +1,13 @@
 use anyhow::Result;
 use ah_cli::{AgentCommands, Cli, Commands, Parser};
+use ah_core::DatabaseManager;
+use ah_tracing_events::EventsLayer;
+use tracing_subscriber::prelude::*;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    init_tracing();
+
     let cli = Cli::parse();
 
     match cli.command {
@@ -14,3 +19,19 @@ async fn main() -> Result<()> {
         Commands::Tui(args) => args.run().await,
     }
 }
+
+/// Install the global tracing subscriber: human-readable output on stderr,
+/// plus (when the state database is reachable) structured events streamed
+/// into the `events` table so the TUI can replay a session's activity.
+fn init_tracing() {
+    let registry = tracing_subscriber::registry().with(tracing_subscriber::fmt::layer());
+
+    match DatabaseManager::new() {
+        Ok(db) => {
+            let _ = tracing::subscriber::set_global_default(registry.with(EventsLayer::new(db)));
+        }
+        Err(_) => {
+            let _ = tracing::subscriber::set_global_default(registry);
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::sandbox::{parse_bool_flag, prepare_workspace_with_fallback};
 use anyhow::{Context, Result};
 use ah_core::{
     devshell_names, edit_content_interactive, parse_push_to_remote_flag, AgentTasks,
-    DatabaseManager, EditorError, PushHandler, PushOptions,
+    DatabaseManager, EditorError, ForgeConfig, PushHandler, PushOptions,
 };
 use ah_fs_snapshots::PreparedWorkspace;
 use ah_local_db::{FsSnapshotRecord, SessionRecord, TaskRecord};
@@ -215,6 +215,10 @@ impl TaskCreateArgs {
             .create_session(&session_record)
             .context("Failed to create session record")?;
 
+        // Funnel this task's structured logs into the session's event
+        // timeline for the rest of task creation.
+        let _session_guard = ah_tracing_events::attach_session(session_id.clone());
+
         // Create task record
         let task_record = TaskRecord {
             id: 0, // Will be set by autoincrement
@@ -264,12 +268,31 @@ impl TaskCreateArgs {
         }
 
         // Handle push operations
-        if let Some(push_flag) = &self.push_to_remote {
+        let pushed = if let Some(push_flag) = &self.push_to_remote {
             let push_bool =
                 parse_push_to_remote_flag(push_flag).context("Invalid --push-to-remote value")?;
-            self.handle_push(&actual_branch_name, Some(push_bool)).await?;
+            self.handle_push(&actual_branch_name, Some(push_bool)).await?
         } else if !self.non_interactive {
-            self.handle_push(&actual_branch_name, None).await?;
+            self.handle_push(&actual_branch_name, None).await?
+        } else {
+            false
+        };
+
+        // Opt-in pull-request automation (AH_OPEN_PR): only meaningful once
+        // the branch actually exists on the remote.
+        if pushed {
+            let forge_config = ForgeConfig::from_env();
+            if start_new_branch {
+                tasks
+                    .open_pull_request(&task_content, &actual_branch_name, &forge_config)
+                    .await
+                    .context("Failed to open pull request")?;
+            } else {
+                tasks
+                    .update_pull_request(&forge_config)
+                    .await
+                    .context("Failed to update pull request")?;
+            }
         }
 
         // Success - don't cleanup branch
@@ -344,19 +367,20 @@ impl TaskCreateArgs {
         }
     }
 
-    /// Handle push operations
-    async fn handle_push(&self, branch_name: &str, explicit_push: Option<bool>) -> Result<()> {
+    /// Handle push operations. Returns whether the branch was actually
+    /// pushed, so callers can decide whether it's safe to open a PR for it.
+    async fn handle_push(&self, branch_name: &str, explicit_push: Option<bool>) -> Result<bool> {
         let push_handler =
             PushHandler::new(".").await.context("Failed to initialize push handler")?;
 
         let options = PushOptions::new(branch_name.to_string()).with_push_to_remote(explicit_push);
 
-        push_handler
+        let pushed = push_handler
             .handle_push(&options)
             .await
             .context("Failed to handle push operation")?;
 
-        Ok(())
+        Ok(pushed)
     }
 
     /// Cleanup a branch that was created but task recording failed
@@ -26,6 +26,31 @@ fn test_cli_parsing_init_session() {
     ));
 }
 
+#[test]
+fn test_cli_parsing_benchmark() {
+    let args = vec![
+        "ah",
+        "agent",
+        "fs",
+        "benchmark",
+        "--path",
+        "/path/to/repo",
+        "--samples",
+        "5",
+        "--json",
+    ];
+
+    let cli = Cli::try_parse_from(args).unwrap();
+    assert!(matches!(
+        cli.command,
+        Commands::Agent {
+            subcommand: AgentCommands::Fs {
+                subcommand: AgentFsCommands::Benchmark(_)
+            }
+        }
+    ));
+}
+
 #[test]
 fn test_cli_parsing_snapshots() {
     let args = vec!["ah", "agent", "fs", "snapshots", "my-session-id"];
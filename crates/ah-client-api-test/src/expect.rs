@@ -0,0 +1,153 @@
+//! `ExpectedTask` builder for [`crate::harness::ClientApiTestHarness::run`]:
+//! matches a task observed during a harness run by name or id, and asserts
+//! its final status, metadata, and the order it transitioned through
+//! statuses in.
+
+use crate::harness::ObservedTask;
+use ah_core::task::{TaskId, TaskStatus};
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Name(String),
+    Id(TaskId),
+}
+
+/// One task a harness run expects to observe, plus the assertions it must
+/// satisfy. Built fluently and passed to
+/// [`crate::harness::ClientApiTestHarness::run`] as a `Vec<ExpectedTask>`.
+#[derive(Debug, Clone)]
+pub struct ExpectedTask {
+    matcher: Matcher,
+    expected_status: Option<TaskStatus>,
+    expected_metadata_keys: Vec<String>,
+    expected_transitions: Option<Vec<TaskStatus>>,
+}
+
+impl ExpectedTask {
+    /// Match the task created with this name (the `prompt` passed to
+    /// `create_task`).
+    pub fn named(name: impl Into<String>) -> Self {
+        Self {
+            matcher: Matcher::Name(name.into()),
+            expected_status: None,
+            expected_metadata_keys: Vec::new(),
+            expected_transitions: None,
+        }
+    }
+
+    /// Match the task with this id, for tests that already captured it from
+    /// a prior `create_task` response.
+    pub fn with_id(id: TaskId) -> Self {
+        Self {
+            matcher: Matcher::Id(id),
+            expected_status: None,
+            expected_metadata_keys: Vec::new(),
+            expected_transitions: None,
+        }
+    }
+
+    /// Require the task's final status to be exactly `status`.
+    pub fn expect_status(mut self, status: TaskStatus) -> Self {
+        self.expected_status = Some(status);
+        self
+    }
+
+    /// Require the task's metadata to contain `key`, regardless of value.
+    pub fn expect_metadata_key(mut self, key: impl Into<String>) -> Self {
+        self.expected_metadata_keys.push(key.into());
+        self
+    }
+
+    /// Require the task to have transitioned through exactly this sequence
+    /// of statuses, in order, as observed via `watch_tasks` (including the
+    /// status it was created with).
+    pub fn expect_transitions(mut self, transitions: Vec<TaskStatus>) -> Self {
+        self.expected_transitions = Some(transitions);
+        self
+    }
+
+    fn matches(&self, observed: &ObservedTask) -> bool {
+        match &self.matcher {
+            Matcher::Name(name) => observed.task.name == *name,
+            Matcher::Id(id) => observed.task.id == *id,
+        }
+    }
+
+    fn describe_matcher(&self) -> String {
+        match &self.matcher {
+            Matcher::Name(name) => format!("task named {name:?}"),
+            Matcher::Id(id) => format!("task #{}", id.0),
+        }
+    }
+}
+
+/// Check every `expected` task against `observed`, panicking with a rich
+/// diff of every violation (unmatched expectations and mismatched
+/// assertions alike) if any is found.
+pub(crate) fn verify(expected: &[ExpectedTask], observed: &[ObservedTask]) {
+    let mut failures = Vec::new();
+
+    for exp in expected {
+        match observed.iter().find(|obs| exp.matches(obs)) {
+            None => failures.push(format!("- {} was never observed", exp.describe_matcher())),
+            Some(obs) => {
+                if let Some(expected_status) = exp.expected_status {
+                    if obs.task.status != expected_status {
+                        failures.push(format!(
+                            "- {}: expected final status {:?}, got {:?}",
+                            exp.describe_matcher(),
+                            expected_status,
+                            obs.task.status
+                        ));
+                    }
+                }
+
+                for key in &exp.expected_metadata_keys {
+                    if !obs.task.metadata.contains_key(key) {
+                        failures.push(format!(
+                            "- {}: expected metadata key {:?}, metadata was {:?}",
+                            exp.describe_matcher(),
+                            key,
+                            obs.task.metadata
+                        ));
+                    }
+                }
+
+                if let Some(expected_transitions) = &exp.expected_transitions {
+                    if &obs.transitions != expected_transitions {
+                        failures.push(format!(
+                            "- {}: expected transitions {:?}, observed {:?}",
+                            exp.describe_matcher(),
+                            expected_transitions,
+                            obs.transitions
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!(
+            "ClientApiTestHarness expectations violated:\n{}\n\nall observed tasks:\n{}",
+            failures.join("\n"),
+            describe_observed(observed),
+        );
+    }
+}
+
+fn describe_observed(observed: &[ObservedTask]) -> String {
+    if observed.is_empty() {
+        return "  (none)".to_string();
+    }
+    observed
+        .iter()
+        .map(|obs| {
+            format!(
+                "  - #{} {:?} status={:?} transitions={:?} metadata={:?}",
+                obs.task.id.0, obs.task.name, obs.task.status, obs.transitions, obs.task.metadata
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
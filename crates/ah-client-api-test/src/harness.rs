@@ -0,0 +1,330 @@
+//! Drives a [`ClientApi`] consumer against an in-process server over a
+//! [`tokio::io::duplex`] stream, modeled on `console-subscriber`'s
+//! in-memory-transport integration tests: both ends run on the test's own
+//! runtime, so a harness run is deterministic and needs no real listening
+//! socket.
+
+use crate::expect::{self, ExpectedTask};
+use crate::protocol::{self, Call, CallResult, Frame};
+use crate::server::DuplexServer;
+use ah_client_api::{ClientApi, ClientApiError, ClientApiResult};
+use ah_core::task::{Task, TaskUpdate};
+use ah_rest_api_contract::{
+    AgentCapability, ConfigureRequest, ConfigureResponse, CreateTaskRequest, CreateTaskResponse,
+    DaemonInfo, Project, Repository,
+};
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex};
+use tokio_stream::wrappers::BroadcastStream;
+
+/// Capacity of the in-process `watch_tasks` broadcast channel used internally
+/// by [`DuplexClientApi`]; generous since a harness run is short-lived and
+/// single-client.
+const UPDATE_CHANNEL_CAPACITY: usize = 256;
+
+/// A task as observed by a [`ClientApiTestHarness`] run: its final state
+/// (fetched directly from the server's task manager once the driven future
+/// completes) plus the sequence of statuses it was seen transitioning
+/// through on the `watch_tasks` stream.
+#[derive(Debug, Clone)]
+pub(crate) struct ObservedTask {
+    pub task: Task,
+    pub transitions: Vec<ah_core::task::TaskStatus>,
+}
+
+/// Test harness that wires a [`ClientApi`] to an in-process, in-memory
+/// server (see [`crate::server::DuplexServer`]) over a [`tokio::io::duplex`]
+/// pair.
+///
+/// Seed fixture data with [`Self::with_projects`]/[`Self::with_repositories`]/
+/// [`Self::with_agents`], then call [`Self::run`] with the `ClientApi` usage
+/// under test and the [`ExpectedTask`]s it should produce.
+#[derive(Debug, Default)]
+pub struct ClientApiTestHarness {
+    server: DuplexServer,
+}
+
+impl ClientApiTestHarness {
+    /// Create a harness with no fixture data and an empty task list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Seed the projects `list_projects` returns.
+    pub fn with_projects(mut self, projects: Vec<Project>) -> Self {
+        self.server.projects = projects;
+        self
+    }
+
+    /// Seed the repositories `list_repositories` returns.
+    pub fn with_repositories(mut self, repositories: Vec<Repository>) -> Self {
+        self.server.repositories = repositories;
+        self
+    }
+
+    /// Seed the agent capabilities `list_agents` returns.
+    pub fn with_agents(mut self, agents: Vec<AgentCapability>) -> Self {
+        self.server.agents = agents;
+        self
+    }
+
+    /// Drive `f` to completion against a [`ClientApi`] backed by the
+    /// in-process server, recording every `TaskUpdate` it streams, then
+    /// check `expected` against the resulting tasks.
+    ///
+    /// # Panics
+    /// Panics with a rich diff of every violation if any `expected` task was
+    /// never observed, or was observed but failed one of its assertions.
+    pub async fn run<F, Fut>(self, expected: Vec<ExpectedTask>, f: F)
+    where
+        F: FnOnce(Arc<dyn ClientApi>) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let server = Arc::new(self.server);
+        let (client_side, server_side) = tokio::io::duplex(64 * 1024);
+
+        let server_for_task = server.clone();
+        tokio::spawn(async move { server_for_task.serve(server_side).await });
+
+        let client: Arc<dyn ClientApi> = DuplexClientApi::connect(client_side);
+
+        // The harness watches task updates itself, independently of whatever
+        // the driven future does, so transition ordering can be asserted
+        // even if `f` never calls `watch_tasks` on its own.
+        let mut updates = client
+            .watch_tasks(None)
+            .await
+            .expect("harness's own watch_tasks subscription should never fail");
+        let recorded: Arc<Mutex<Vec<TaskUpdate>>> = Arc::new(Mutex::new(Vec::new()));
+        let recorder = recorded.clone();
+        let collector = tokio::spawn(async move {
+            while let Some(update) = updates.next().await {
+                recorder.lock().await.push(update);
+            }
+        });
+
+        f(client).await;
+
+        // Let the collector drain whatever the server has already pushed
+        // for work `f` triggered before returning; nothing it emits after
+        // this point could belong to that work, since `f` already observed
+        // its own completion.
+        tokio::task::yield_now().await;
+        collector.abort();
+
+        let final_tasks = server
+            .task_manager
+            .list_tasks()
+            .await
+            .expect("harness's in-memory task manager never returns an error");
+        let recorded = recorded.lock().await;
+        let observed = reconstruct(final_tasks, &recorded);
+
+        expect::verify(&expected, &observed);
+    }
+}
+
+fn reconstruct(final_tasks: Vec<Task>, recorded: &[TaskUpdate]) -> Vec<ObservedTask> {
+    final_tasks
+        .into_iter()
+        .map(|task| {
+            let transitions = recorded
+                .iter()
+                .filter(|update| update.id == task.id)
+                .map(|update| update.status)
+                .collect();
+            ObservedTask { task, transitions }
+        })
+        .collect()
+}
+
+/// Client-side half of the duplex connection: implements [`ClientApi`] by
+/// sending [`Call`]s as [`Frame::Request`]s and awaiting the matching
+/// [`Frame::Response`], demultiplexing unsolicited [`Frame::Update`]s into an
+/// internal broadcast channel that `watch_tasks` subscribers read from.
+struct DuplexClientApi {
+    write_tx: mpsc::Sender<Frame>,
+    next_id: AtomicU64,
+    pending: StdMutex<HashMap<u64, oneshot::Sender<CallResult>>>,
+    updates: broadcast::Sender<TaskUpdate>,
+}
+
+impl DuplexClientApi {
+    fn connect<S>(stream: S) -> Arc<Self>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
+    {
+        let (read_half, write_half) = tokio::io::split(stream);
+        let (write_tx, mut write_rx) = mpsc::channel::<Frame>(64);
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+
+        let this = Arc::new(Self {
+            write_tx,
+            next_id: AtomicU64::new(0),
+            pending: StdMutex::new(HashMap::new()),
+            updates,
+        });
+
+        tokio::spawn(async move {
+            let mut write_half = write_half;
+            while let Some(frame) = write_rx.recv().await {
+                if protocol::write_frame(&mut write_half, &frame)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let reader_handle = this.clone();
+        tokio::spawn(async move {
+            let mut reader = protocol::buffered(read_half);
+            loop {
+                match protocol::read_frame(&mut reader).await {
+                    Ok(Some(Frame::Response { id, result })) => {
+                        if let Some(tx) = reader_handle.pending.lock().unwrap().remove(&id) {
+                            let _ = tx.send(result);
+                        }
+                    }
+                    Ok(Some(Frame::Update(update))) => {
+                        let _ = reader_handle.updates.send(update);
+                    }
+                    Ok(Some(Frame::Request { .. })) | Ok(None) | Err(_) => break,
+                }
+            }
+        });
+
+        this
+    }
+
+    async fn call(&self, call: Call) -> ClientApiResult<CallResult> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+
+        self.write_tx
+            .send(Frame::Request { id, call })
+            .await
+            .map_err(|_| ClientApiError::Unexpected("server connection closed".into()))?;
+
+        rx.await.map_err(|_| {
+            ClientApiError::Unexpected("server connection closed before responding".into())
+        })
+    }
+}
+
+#[async_trait]
+impl ClientApi for DuplexClientApi {
+    async fn list_projects(&self, tenant_id: Option<&str>) -> ClientApiResult<Vec<Project>> {
+        match self
+            .call(Call::ListProjects {
+                tenant_id: tenant_id.map(String::from),
+            })
+            .await?
+        {
+            CallResult::Projects(projects) => Ok(projects),
+            CallResult::Error(message) => Err(ClientApiError::Server(message)),
+            _ => Err(ClientApiError::Unexpected(
+                "unexpected response to ListProjects".into(),
+            )),
+        }
+    }
+
+    async fn list_repositories(
+        &self,
+        tenant_id: Option<&str>,
+        project_id: Option<&str>,
+    ) -> ClientApiResult<Vec<Repository>> {
+        match self
+            .call(Call::ListRepositories {
+                tenant_id: tenant_id.map(String::from),
+                project_id: project_id.map(String::from),
+            })
+            .await?
+        {
+            CallResult::Repositories(repositories) => Ok(repositories),
+            CallResult::Error(message) => Err(ClientApiError::Server(message)),
+            _ => Err(ClientApiError::Unexpected(
+                "unexpected response to ListRepositories".into(),
+            )),
+        }
+    }
+
+    async fn list_agents(&self) -> ClientApiResult<Vec<AgentCapability>> {
+        match self.call(Call::ListAgents).await? {
+            CallResult::Agents(agents) => Ok(agents),
+            CallResult::Error(message) => Err(ClientApiError::Server(message)),
+            _ => Err(ClientApiError::Unexpected(
+                "unexpected response to ListAgents".into(),
+            )),
+        }
+    }
+
+    async fn create_task(
+        &self,
+        request: &CreateTaskRequest,
+    ) -> ClientApiResult<CreateTaskResponse> {
+        match self
+            .call(Call::CreateTask(Box::new(request.clone())))
+            .await?
+        {
+            CallResult::Task(response) => Ok(response),
+            CallResult::Error(message) => Err(ClientApiError::Server(message)),
+            _ => Err(ClientApiError::Unexpected(
+                "unexpected response to CreateTask".into(),
+            )),
+        }
+    }
+
+    async fn daemon_info(&self) -> ClientApiResult<DaemonInfo> {
+        match self.call(Call::DaemonInfo).await? {
+            CallResult::Daemon(info) => Ok(info),
+            CallResult::Error(message) => Err(ClientApiError::Server(message)),
+            _ => Err(ClientApiError::Unexpected(
+                "unexpected response to DaemonInfo".into(),
+            )),
+        }
+    }
+
+    async fn configure(&self, request: &ConfigureRequest) -> ClientApiResult<ConfigureResponse> {
+        match self
+            .call(Call::Configure(Box::new(request.clone())))
+            .await?
+        {
+            CallResult::Configured(response) => Ok(response),
+            CallResult::Error(message) => Err(ClientApiError::Server(message)),
+            _ => Err(ClientApiError::Unexpected(
+                "unexpected response to Configure".into(),
+            )),
+        }
+    }
+
+    async fn watch_tasks(
+        &self,
+        tenant_id: Option<&str>,
+    ) -> ClientApiResult<BoxStream<'static, TaskUpdate>> {
+        match self
+            .call(Call::WatchTasks {
+                tenant_id: tenant_id.map(String::from),
+            })
+            .await?
+        {
+            CallResult::Watching => {
+                let rx = self.updates.subscribe();
+                Ok(BroadcastStream::new(rx)
+                    .filter_map(|item| async move { item.ok() })
+                    .boxed())
+            }
+            CallResult::Error(message) => Err(ClientApiError::Server(message)),
+            _ => Err(ClientApiError::Unexpected(
+                "unexpected response to WatchTasks".into(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! In-process test tooling for [`ah_client_api::ClientApi`] consumers (the
+//! TUI, primarily), so they can be tested without standing up a real REST
+//! server.
+//!
+//! Two independent ways to test against `ClientApi`, matching two different
+//! needs:
+//!
+//! - [`ClientApiTestHarness`] wires a `ClientApi` to an in-process,
+//!   in-memory server over a [`tokio::io::duplex`] stream (modeled on
+//!   `console-subscriber`'s integration-test approach), for
+//!   deterministic end-to-end exercising of task creation and the
+//!   `watch_tasks` stream. Pair it with [`ExpectedTask`] to assert on the
+//!   tasks it observes.
+//! - [`MockClientApi`] is a `mockall`-generated mock for stubbing individual
+//!   methods in unit tests that don't need a running server at all.
+//!
+//! Not to be confused with `ah-test-scenarios`/`ah-tui-test`, which drive the
+//! TUI's *rendering* against scripted scenario files and a hand-written
+//! `ah-rest-client-mock::MockClient` — this crate operates one level below
+//! that, at the `ClientApi` trait itself.
+
+mod expect;
+mod harness;
+mod mock;
+mod protocol;
+mod server;
+
+pub use expect::ExpectedTask;
+pub use harness::ClientApiTestHarness;
+pub use mock::MockClientApi;
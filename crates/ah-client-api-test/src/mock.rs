@@ -0,0 +1,36 @@
+//! `mockall`-generated [`MockClientApi`] for unit tests that want to stub
+//! individual [`ClientApi`] methods (e.g. `create_task`/`list_agents`)
+//! without standing up a [`crate::harness::ClientApiTestHarness`].
+//!
+//! `ClientApi` isn't `#[automock]`-annotated (it lives in `ah-client-api`,
+//! which shouldn't take a dev-only dependency on `mockall`), so the mock is
+//! built with `mock!` instead, re-declaring the trait's async methods
+//! verbatim under `#[async_trait]`.
+
+use ah_client_api::{ClientApi, ClientApiResult};
+use ah_core::task::TaskUpdate;
+use ah_rest_api_contract::{
+    AgentCapability, ConfigureRequest, ConfigureResponse, CreateTaskRequest, CreateTaskResponse,
+    DaemonInfo, Project, Repository,
+};
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+
+mockall::mock! {
+    pub ClientApi {}
+
+    #[async_trait]
+    impl ClientApi for ClientApi {
+        async fn list_projects(&self, tenant_id: Option<&str>) -> ClientApiResult<Vec<Project>>;
+        async fn list_repositories(
+            &self,
+            tenant_id: Option<&str>,
+            project_id: Option<&str>,
+        ) -> ClientApiResult<Vec<Repository>>;
+        async fn list_agents(&self) -> ClientApiResult<Vec<AgentCapability>>;
+        async fn create_task(&self, request: &CreateTaskRequest) -> ClientApiResult<CreateTaskResponse>;
+        async fn daemon_info(&self) -> ClientApiResult<DaemonInfo>;
+        async fn configure(&self, request: &ConfigureRequest) -> ClientApiResult<ConfigureResponse>;
+        async fn watch_tasks(&self, tenant_id: Option<&str>) -> ClientApiResult<BoxStream<'static, TaskUpdate>>;
+    }
+}
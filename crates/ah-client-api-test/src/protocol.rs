@@ -0,0 +1,93 @@
+//! Wire protocol spoken over the [`tokio::io::duplex`] pair that connects a
+//! [`crate::server::DuplexServer`] to a [`crate::harness::ClientApiTestHarness`].
+//!
+//! Frames are newline-delimited JSON, the same framing convention used by
+//! `ah-fs-snapshots-daemon`'s Unix-socket protocol. A single connection
+//! multiplexes request/response pairs (keyed by `id`) with the unsolicited
+//! `TaskUpdate` stream produced by `watch_tasks`, so one duplex pair is
+//! enough to stand in for the whole [`ah_client_api::ClientApi`] surface.
+
+use ah_core::task::TaskUpdate;
+use ah_rest_api_contract::{
+    AgentCapability, ConfigureRequest, ConfigureResponse, CreateTaskRequest, CreateTaskResponse,
+    DaemonInfo, Project, Repository,
+};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+/// A single [`ah_client_api::ClientApi`] method invocation, tagged with the
+/// data needed to perform it.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Call {
+    ListProjects {
+        tenant_id: Option<String>,
+    },
+    ListRepositories {
+        tenant_id: Option<String>,
+        project_id: Option<String>,
+    },
+    ListAgents,
+    CreateTask(Box<CreateTaskRequest>),
+    DaemonInfo,
+    Configure(Box<ConfigureRequest>),
+    WatchTasks {
+        tenant_id: Option<String>,
+    },
+}
+
+/// The outcome of a [`Call`], mirroring one arm per successful return type
+/// plus a catch-all `Error` carrying the stringified failure.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum CallResult {
+    Projects(Vec<Project>),
+    Repositories(Vec<Repository>),
+    Agents(Vec<AgentCapability>),
+    Task(CreateTaskResponse),
+    Daemon(DaemonInfo),
+    Configured(ConfigureResponse),
+    Watching,
+    Error(String),
+}
+
+/// A single line of the duplex wire protocol.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) enum Frame {
+    /// A client-issued call, to be answered with a [`Frame::Response`]
+    /// carrying the same `id`.
+    Request { id: u64, call: Call },
+    /// The server's answer to a [`Frame::Request`].
+    Response { id: u64, result: CallResult },
+    /// An unsolicited task update, pushed by the server for every live
+    /// `WatchTasks` subscription once it has replied `Watching`.
+    Update(TaskUpdate),
+}
+
+/// Serialize `frame` as one line of JSON and write it to `writer`.
+pub(crate) async fn write_frame<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    frame: &Frame,
+) -> std::io::Result<()> {
+    let mut line = serde_json::to_string(frame).expect("Frame is always serializable");
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await
+}
+
+/// Read and deserialize the next line-delimited [`Frame`] from `reader`, or
+/// `Ok(None)` once the peer has closed its half of the duplex.
+pub(crate) async fn read_frame<R: AsyncBufReadExt + Unpin>(
+    reader: &mut R,
+) -> std::io::Result<Option<Frame>> {
+    let mut line = String::new();
+    let n = reader.read_line(&mut line).await?;
+    if n == 0 {
+        return Ok(None);
+    }
+    let frame = serde_json::from_str(line.trim_end())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(Some(frame))
+}
+
+/// Wrap a raw reader half in the [`BufReader`] that [`read_frame`] expects.
+pub(crate) fn buffered<R: tokio::io::AsyncRead>(reader: R) -> BufReader<R> {
+    BufReader::new(reader)
+}
@@ -0,0 +1,147 @@
+//! The in-process stand-in for a real AH REST service, driven by an
+//! in-memory [`TaskManager`] and speaking [`crate::protocol`] frames over one
+//! half of a [`tokio::io::duplex`] pair.
+
+use crate::protocol::{self, Call, CallResult, Frame};
+use ah_core::task::TaskManager;
+use ah_rest_api_contract::{
+    AgentCapability, ConfigureResponse, CreateTaskRequest, CreateTaskResponse, DaemonInfo,
+    Project, Repository, SessionStatus, TaskLinks,
+};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+
+/// Fixture data and the [`TaskManager`] backing a [`DuplexServer`]. Spawned
+/// by [`crate::harness::ClientApiTestHarness::run`]; tests don't construct
+/// this directly, but reach it (for task-related assertions) through the
+/// harness.
+#[derive(Debug, Default)]
+pub(crate) struct DuplexServer {
+    pub(crate) task_manager: Arc<TaskManager>,
+    pub(crate) projects: Vec<Project>,
+    pub(crate) repositories: Vec<Repository>,
+    pub(crate) agents: Vec<AgentCapability>,
+}
+
+impl DuplexServer {
+    /// Drive the server side of a duplex connection until the peer closes
+    /// its half.
+    pub(crate) async fn serve<S>(self: Arc<Self>, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let (read_half, write_half) = tokio::io::split(stream);
+        let mut reader = protocol::buffered(read_half);
+
+        let (write_tx, mut write_rx) = mpsc::channel::<Frame>(64);
+        let writer_task = tokio::spawn(async move {
+            let mut write_half = write_half;
+            while let Some(frame) = write_rx.recv().await {
+                if protocol::write_frame(&mut write_half, &frame)
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        loop {
+            let frame = match protocol::read_frame(&mut reader).await {
+                Ok(Some(frame)) => frame,
+                _ => break,
+            };
+
+            let Frame::Request { id, call } = frame else {
+                // The server never issues Request frames of its own, so a
+                // Response/Update here would indicate a protocol bug on the
+                // client side; ignore rather than tear down the connection.
+                continue;
+            };
+
+            if matches!(call, Call::WatchTasks { .. }) {
+                if write_tx
+                    .send(Frame::Response {
+                        id,
+                        result: CallResult::Watching,
+                    })
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+                self.spawn_update_forwarder(write_tx.clone());
+                continue;
+            }
+
+            let result = self.handle_call(call).await;
+            if write_tx.send(Frame::Response { id, result }).await.is_err() {
+                break;
+            }
+        }
+
+        drop(write_tx);
+        let _ = writer_task.await;
+    }
+
+    fn spawn_update_forwarder(&self, write_tx: mpsc::Sender<Frame>) {
+        let task_manager = self.task_manager.clone();
+        tokio::spawn(async move {
+            let mut updates = Box::pin(task_manager.subscribe().await);
+            while let Some(update) = updates.next().await {
+                if write_tx.send(Frame::Update(update)).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    async fn handle_call(&self, call: Call) -> CallResult {
+        match call {
+            Call::ListProjects { .. } => CallResult::Projects(self.projects.clone()),
+            Call::ListRepositories { .. } => CallResult::Repositories(self.repositories.clone()),
+            Call::ListAgents => CallResult::Agents(self.agents.clone()),
+            Call::CreateTask(request) => match self.create_task(*request).await {
+                Ok(response) => CallResult::Task(response),
+                Err(message) => CallResult::Error(message),
+            },
+            // The harness has no real daemon to describe or configure, so
+            // these answer with a fixed, always-succeeding stand-in rather
+            // than being wired to `DuplexServer` state like the list
+            // fixtures above.
+            Call::DaemonInfo => CallResult::Daemon(DaemonInfo {
+                version: "0.0.0-harness".into(),
+                api_version: "v1".into(),
+                build: "test".into(),
+                capabilities: vec!["tasks".into()],
+            }),
+            Call::Configure(_) => CallResult::Configured(ConfigureResponse {
+                applied: true,
+                warnings: Vec::new(),
+            }),
+            Call::WatchTasks { .. } => {
+                unreachable!("WatchTasks is answered by the caller before reaching handle_call")
+            }
+        }
+    }
+
+    async fn create_task(&self, request: CreateTaskRequest) -> Result<CreateTaskResponse, String> {
+        let id = self
+            .task_manager
+            .create_task(request.prompt.clone(), request.prompt.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(CreateTaskResponse {
+            id: id.0.to_string(),
+            status: SessionStatus::Queued,
+            links: TaskLinks {
+                self_link: format!("/tasks/{}", id.0),
+                events: format!("/tasks/{}/events", id.0),
+                logs: format!("/tasks/{}/logs", id.0),
+            },
+        })
+    }
+}
@@ -1,7 +1,9 @@
 //! Client API trait for AH TUI
 
 use async_trait::async_trait;
+use ah_core::task::TaskUpdate;
 use ah_rest_api_contract::*;
+use futures::stream::BoxStream;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -26,4 +28,23 @@ pub trait ClientApi: Send + Sync {
 
     async fn create_task(&self, request: &CreateTaskRequest)
         -> ClientApiResult<CreateTaskResponse>;
+
+    /// Describe the daemon/service backing this client: build, API
+    /// version, and enabled capabilities. Lets a client detect feature
+    /// support (e.g. before calling [`Self::configure`]) without hard
+    /// version-gating against a release number.
+    async fn daemon_info(&self) -> ClientApiResult<DaemonInfo>;
+
+    /// Apply a runtime configuration change to the daemon/service backing
+    /// this client.
+    async fn configure(&self, request: &ConfigureRequest) -> ClientApiResult<ConfigureResponse>;
+
+    /// Subscribe to live task updates. The returned stream starts with a
+    /// snapshot of currently known tasks, then yields further updates as
+    /// they happen, so the TUI can render a consistent list without a
+    /// separate initial `list` call.
+    async fn watch_tasks(
+        &self,
+        tenant_id: Option<&str>,
+    ) -> ClientApiResult<BoxStream<'static, TaskUpdate>>;
 }
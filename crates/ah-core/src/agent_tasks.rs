@@ -3,12 +3,17 @@
 //! This module provides functionality for managing agent task files in VCS repositories,
 //! including creating initial tasks, appending follow-up tasks, and detecting task branches.
 //! This is a direct port of the Ruby AgentTasks class functionality.
+//!
+//! Opening and keeping a pull request in sync with a task branch is a
+//! separate, opt-in concern handled by [`Self::open_pull_request`]/
+//! [`Self::update_pull_request`] (see [`crate::forge`]).
 
 use ah_repo::{VcsRepo, VcsResult};
 use chrono::{DateTime, Datelike, Timelike, Utc};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Manages agent task files in a VCS repository.
 ///
@@ -245,4 +250,94 @@ impl AgentTasks {
     pub fn repo(&self) -> &VcsRepo {
         &self.repo
     }
+
+    /// Build the [`ah_forge::Forge`] for this repo's default remote, if PR
+    /// automation is enabled and the forge can be detected/configured.
+    fn forge(&self, config: &crate::forge::ForgeConfig) -> crate::Result<Option<Arc<dyn ah_forge::Forge>>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let remote_url = match self.repo.default_remote_http_url()? {
+            Some(url) => url,
+            None => return Ok(None),
+        };
+
+        Ok(crate::forge::forge_for_remote(&remote_url, config)?)
+    }
+
+    /// Open a draft pull request for the current task branch, seeded from
+    /// `task_content`, and attach its URL to the start commit as a
+    /// `Pull-Request:` trailer so a later [`Self::update_pull_request`] call
+    /// can find and refresh it. A no-op (returning `Ok(None)`) unless
+    /// `forge_config.enabled` and the `origin` remote's forge can be
+    /// determined.
+    ///
+    /// Call this only once the branch has actually been pushed to its
+    /// remote (e.g. via [`crate::push::PushHandler`]) — the PR's `head`
+    /// branch must already exist on the forge.
+    ///
+    /// # Errors
+    /// Returns an error if not on a task branch, or if PR automation is
+    /// enabled but opening the pull request fails.
+    pub async fn open_pull_request(
+        &self,
+        task_content: &str,
+        branch_name: &str,
+        forge_config: &crate::forge::ForgeConfig,
+    ) -> crate::Result<Option<ah_forge::PullRequest>> {
+        let forge = match self.forge(forge_config)? {
+            Some(forge) => forge,
+            None => return Ok(None),
+        };
+
+        let pr = forge
+            .open_pull_request(
+                &format!("Agent task: {branch_name}"),
+                task_content,
+                branch_name,
+                self.repo.default_branch(),
+            )
+            .await?;
+
+        let start_commit = self.repo.latest_agent_branch_commit()?;
+        if let Some(message) = self.repo.commit_message(&start_commit)? {
+            self.repo
+                .amend_commit_message(&crate::forge::append_pull_request_trailer(&message, &pr))?;
+        }
+
+        Ok(Some(pr))
+    }
+
+    /// If a PR was previously opened for the current task branch (see
+    /// [`Self::open_pull_request`]), refresh its description from the full,
+    /// up-to-date task file content. A no-op if PR automation is disabled or
+    /// no PR was opened for this branch.
+    ///
+    /// # Errors
+    /// Returns an error if not on a task branch, or if PR automation is
+    /// enabled, a PR exists, and updating it fails.
+    pub async fn update_pull_request(&self, forge_config: &crate::forge::ForgeConfig) -> crate::Result<()> {
+        let forge = match self.forge(forge_config)? {
+            Some(forge) => forge,
+            None => return Ok(()),
+        };
+
+        let start_commit = self.repo.latest_agent_branch_commit()?;
+        let message = match self.repo.commit_message(&start_commit)? {
+            Some(message) => message,
+            None => return Ok(()),
+        };
+
+        let pr = match crate::forge::extract_pull_request(&message) {
+            Some(pr) => pr,
+            None => return Ok(()), // No PR was opened for this task branch.
+        };
+
+        let task_file = self.agent_task_file_in_current_branch()?;
+        let full_body = fs::read_to_string(&task_file)?;
+        forge.update_pull_request(&pr, &full_body).await?;
+
+        Ok(())
+    }
 }
@@ -1,10 +1,12 @@
 //! Database integration for task and session persistence.
 
 use ah_local_db::{
-    AgentRecord, AgentStore, Database, FsSnapshotRecord, FsSnapshotStore, RepoRecord, RepoStore,
-    RuntimeStore, SessionRecord, SessionStore, TaskRecord, TaskStore,
+    AgentRecord, AgentStore, Database, ErrorStore, EventRecord, EventStore, FsSnapshotRecord,
+    FsSnapshotStore, RepoRecord, RepoStore, RuntimeStore, SessionRecord, SessionStore, TaskRecord,
+    TaskStateRecord, TaskStateStore, TaskStore,
 };
 use ah_repo::VcsRepo;
+use async_trait::async_trait;
 use std::path::Path;
 
 /// Database manager for AH core operations.
@@ -118,6 +120,26 @@ impl DatabaseManager {
         Ok(snapshot_store.insert(snapshot_record)?)
     }
 
+    /// List filesystem snapshots recorded for a session, oldest first.
+    pub fn list_fs_snapshots(&self, session_id: &str) -> crate::Result<Vec<FsSnapshotRecord>> {
+        let conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+
+        let snapshot_store = FsSnapshotStore::new(&conn);
+        Ok(snapshot_store.list_by_session(session_id)?)
+    }
+
+    /// Fetch a session record by its ID.
+    pub fn get_session(&self, session_id: &str) -> crate::Result<Option<SessionRecord>> {
+        let conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+
+        let session_store = SessionStore::new(&conn);
+        Ok(session_store.get(session_id)?)
+    }
+
     /// Update session status.
     pub fn update_session_status(
         &self,
@@ -133,6 +155,38 @@ impl DatabaseManager {
         Ok(session_store.update_status(session_id, status, ended_at)?)
     }
 
+    /// Record a structured error for post-mortem debugging of a session,
+    /// mapping the `Error` variant to a stable `kind` string so failures can
+    /// later be queried with `SELECT ... FROM errors WHERE session_id = ?`.
+    pub fn record_error(&self, session_id: Option<&str>, error: &crate::Error) -> crate::Result<i64> {
+        let mut conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+
+        let error_store = ErrorStore::new(&conn);
+        Ok(error_store.insert(session_id, error.kind(), &error.to_string(), None)?)
+    }
+
+    /// Record a timestamped event on a session's activity timeline.
+    pub fn record_event(&self, session_id: &str, ts: &str, type_: &str, data: Option<&str>) -> crate::Result<i64> {
+        let mut conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+
+        let event_store = EventStore::new(&conn);
+        Ok(event_store.insert(session_id, ts, type_, data)?)
+    }
+
+    /// List events recorded for a session, oldest first.
+    pub fn list_events(&self, session_id: &str) -> crate::Result<Vec<EventRecord>> {
+        let conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+
+        let event_store = EventStore::new(&conn);
+        Ok(event_store.list_by_session(session_id)?)
+    }
+
     /// Generate a new ULID-style session ID.
     pub fn generate_session_id() -> String {
         uuid::Uuid::new_v4().to_string()
@@ -149,3 +203,110 @@ impl Default for DatabaseManager {
         Self::new().expect("Failed to create default database manager")
     }
 }
+
+/// SQLite-backed [`crate::task::TaskStore`], giving [`crate::task::TaskManager`]
+/// durable task history across process restarts (see
+/// [`crate::task::TaskManager::with_store`]).
+#[derive(Debug, Clone)]
+pub struct SqliteTaskStore {
+    db: Database,
+}
+
+impl SqliteTaskStore {
+    /// Wrap an already-open database.
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Open (creating if needed) the database at `path` and wrap it.
+    pub fn open<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+        Ok(Self::new(Database::open(path)?))
+    }
+}
+
+fn task_to_state_record(task: &crate::task::Task) -> crate::Result<TaskStateRecord> {
+    Ok(TaskStateRecord {
+        id: task.id.0 as i64,
+        name: task.name.clone(),
+        description: task.description.clone(),
+        status: task.status.as_str().to_string(),
+        created_at: task.created_at.to_rfc3339(),
+        updated_at: task.updated_at.to_rfc3339(),
+        metadata: if task.metadata.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&task.metadata)?)
+        },
+    })
+}
+
+fn state_record_to_task(record: TaskStateRecord) -> crate::Result<crate::task::Task> {
+    let metadata = match record.metadata {
+        Some(json) => serde_json::from_str(&json)?,
+        None => std::collections::HashMap::new(),
+    };
+
+    let parse_ts = |s: &str| {
+        chrono::DateTime::parse_from_rfc3339(s)
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+            .map_err(|e| crate::Error::generic(format!("invalid timestamp {:?}: {}", s, e)))
+    };
+
+    Ok(crate::task::Task {
+        id: crate::task::TaskId(record.id as u64),
+        name: record.name,
+        description: record.description,
+        status: crate::task::TaskStatus::parse(&record.status)?,
+        created_at: parse_ts(&record.created_at)?,
+        updated_at: parse_ts(&record.updated_at)?,
+        metadata,
+    })
+}
+
+#[async_trait]
+impl crate::task::TaskStore for SqliteTaskStore {
+    async fn create(&self, task: &crate::task::Task) -> crate::Result<()> {
+        let record = task_to_state_record(task)?;
+        let conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+        Ok(TaskStateStore::new(&conn).insert(&record)?)
+    }
+
+    async fn get(&self, id: crate::task::TaskId) -> crate::Result<Option<crate::task::Task>> {
+        let conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+        match TaskStateStore::new(&conn).get(id.0 as i64)? {
+            Some(record) => Ok(Some(state_record_to_task(record)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn update_status(
+        &self,
+        id: crate::task::TaskId,
+        status: crate::task::TaskStatus,
+        updated_at: chrono::DateTime<chrono::Utc>,
+    ) -> crate::Result<()> {
+        let conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+        Ok(TaskStateStore::new(&conn).update_status(
+            id.0 as i64,
+            status.as_str(),
+            &updated_at.to_rfc3339(),
+        )?)
+    }
+
+    async fn list(&self) -> crate::Result<Vec<crate::task::Task>> {
+        let conn = self.db.connection().lock().map_err(|e| {
+            crate::Error::generic(format!("Failed to acquire database lock: {}", e))
+        })?;
+        TaskStateStore::new(&conn).list()?.into_iter().map(state_record_to_task).collect()
+    }
+
+    async fn load_all(&self) -> crate::Result<Vec<crate::task::Task>> {
+        self.list().await
+    }
+}
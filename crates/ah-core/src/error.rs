@@ -18,6 +18,12 @@ pub enum Error {
     #[error("Database error: {0}")]
     Database(#[from] ah_local_db::Error),
 
+    #[error("VCS error: {0}")]
+    Vcs(#[from] ah_repo::VcsError),
+
+    #[error("Forge error: {0}")]
+    Forge(#[from] ah_forge::Error),
+
     #[error("Generic error: {0}")]
     Generic(String),
 }
@@ -41,4 +47,20 @@ impl Error {
     pub fn generic<S: Into<String>>(message: S) -> Self {
         Self::Generic(message.into())
     }
+
+    /// Stable, lowercase identifier for the error's variant, suitable for
+    /// storing in the `errors.kind` column independent of the (free-form)
+    /// display message.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Task { .. } => "task",
+            Self::Session { .. } => "session",
+            Self::Io(_) => "io",
+            Self::Serde(_) => "serde",
+            Self::Database(_) => "database",
+            Self::Vcs(_) => "vcs",
+            Self::Forge(_) => "forge",
+            Self::Generic(_) => "generic",
+        }
+    }
 }
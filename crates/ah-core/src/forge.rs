@@ -0,0 +1,130 @@
+//! Opt-in pull-request automation on top of [`crate::agent_tasks::AgentTasks`].
+//!
+//! Wraps the forge-agnostic [`ah_forge::Forge`] trait: once a task branch has
+//! been pushed, `AgentTasks` can open a draft PR seeded from the task file
+//! content, and later follow-up tasks keep that PR's description in sync.
+//! Entirely opt-in via [`ForgeConfig::enabled`] so flows that don't set
+//! `AH_OPEN_PR` see no behavior change.
+
+use ah_forge::{detect_forge_kind, parse_owner_repo, Forge, ForgeKind, PullRequest};
+use std::sync::Arc;
+
+/// Environment variable gating PR automation (opt-in, parsed with the same
+/// truthy/falsy rules as `--push-to-remote`).
+pub const OPEN_PR_ENV_VAR: &str = "AH_OPEN_PR";
+/// Base URL of a self-hosted Forgejo instance, consulted when `origin` isn't
+/// `github.com`/`gitlab.com` (the only two hosts [`detect_forge_kind`] can
+/// recognize on its own).
+pub const FORGEJO_URL_ENV_VAR: &str = "AH_FORGEJO_URL";
+
+/// Whether PR automation is enabled for this run, and how to resolve a
+/// Forgejo base URL if `origin` doesn't point at github.com/gitlab.com.
+#[derive(Debug, Clone, Default)]
+pub struct ForgeConfig {
+    pub enabled: bool,
+    pub forgejo_base_url: Option<String>,
+}
+
+impl ForgeConfig {
+    /// Read from `AH_OPEN_PR`/`AH_FORGEJO_URL`; disabled unless `AH_OPEN_PR`
+    /// parses as truthy.
+    pub fn from_env() -> Self {
+        let enabled = std::env::var(OPEN_PR_ENV_VAR)
+            .ok()
+            .and_then(|v| crate::push::parse_push_to_remote_flag(&v).ok())
+            .unwrap_or(false);
+        let forgejo_base_url = std::env::var(FORGEJO_URL_ENV_VAR).ok();
+        Self {
+            enabled,
+            forgejo_base_url,
+        }
+    }
+}
+
+/// The trailer line prefix used to persist the PR opened for a task branch
+/// in its start commit's message, alongside the existing `Target-Remote:`/
+/// `Start-Agent-Branch:` trailers (see
+/// [`crate::agent_tasks::AgentTasks::open_pull_request`]). Storing it there
+/// rather than in the local database means it survives the branch being
+/// checked out elsewhere and needs no extra persistence layer.
+pub const PULL_REQUEST_TRAILER: &str = "Pull-Request:";
+
+/// Build a [`Forge`] for `remote_url`, if one can be determined.
+///
+/// Returns `Ok(None)` rather than an error when the forge can't be detected
+/// and no Forgejo base URL was configured, since PR automation is opt-in and
+/// an unrecognized forge shouldn't fail the overall task-creation flow.
+pub fn forge_for_remote(
+    remote_url: &str,
+    config: &ForgeConfig,
+) -> ah_forge::Result<Option<Arc<dyn Forge>>> {
+    match detect_forge_kind(remote_url) {
+        Some(ForgeKind::GitHub) => {
+            let (owner, repo) = parse_owner_repo(remote_url)
+                .ok_or_else(|| ah_forge::Error::UnknownForge(remote_url.to_string()))?;
+            Ok(Some(Arc::new(ah_forge_github::GitHubForge::from_env(owner, repo)?)))
+        }
+        Some(ForgeKind::GitLab) => {
+            let (owner, repo) = parse_owner_repo(remote_url)
+                .ok_or_else(|| ah_forge::Error::UnknownForge(remote_url.to_string()))?;
+            Ok(Some(Arc::new(ah_forge_gitlab::GitLabForge::from_env(owner, repo)?)))
+        }
+        Some(ForgeKind::Forgejo) | None => match &config.forgejo_base_url {
+            Some(base_url) => {
+                let (owner, repo) = parse_owner_repo(remote_url)
+                    .ok_or_else(|| ah_forge::Error::UnknownForge(remote_url.to_string()))?;
+                Ok(Some(Arc::new(ah_forge_forgejo::ForgejoForge::from_env(
+                    base_url.clone(),
+                    owner,
+                    repo,
+                )?)))
+            }
+            None => Ok(None),
+        },
+    }
+}
+
+/// Append a `Pull-Request:` trailer recording `pr` to an existing commit
+/// message.
+pub fn append_pull_request_trailer(commit_message: &str, pr: &PullRequest) -> String {
+    format!("{commit_message}\n{PULL_REQUEST_TRAILER} #{} {}", pr.number, pr.url)
+}
+
+/// Recover the [`PullRequest`] previously stored by
+/// [`append_pull_request_trailer`] from a commit message, if any.
+pub fn extract_pull_request(commit_message: &str) -> Option<PullRequest> {
+    let line = commit_message
+        .lines()
+        .find(|line| line.starts_with(PULL_REQUEST_TRAILER))?
+        .strip_prefix(PULL_REQUEST_TRAILER)?
+        .trim();
+
+    let (number, url) = line.strip_prefix('#')?.split_once(' ')?;
+    Some(PullRequest {
+        number: number.trim().parse().ok()?,
+        url: url.trim().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_pull_request_trailer() {
+        let pr = PullRequest {
+            number: 42,
+            url: "https://github.com/owner/repo/pull/42".to_string(),
+        };
+        let message = append_pull_request_trailer("Start-Agent-Branch: foo", &pr);
+
+        let recovered = extract_pull_request(&message).unwrap();
+        assert_eq!(recovered.number, 42);
+        assert_eq!(recovered.url, pr.url);
+    }
+
+    #[test]
+    fn extract_pull_request_absent_returns_none() {
+        assert!(extract_pull_request("Start-Agent-Branch: foo\nTarget-Remote: bar").is_none());
+    }
+}
@@ -9,6 +9,7 @@ pub mod db;
 pub mod devshell;
 pub mod editor;
 pub mod error;
+pub mod forge;
 pub mod push;
 pub mod session;
 pub mod task;
@@ -20,7 +21,7 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub use error::Error;
 
 /// Task lifecycle management and orchestration.
-pub use task::{Task, TaskId, TaskManager, TaskStatus};
+pub use task::{Task, TaskId, TaskManager, TaskStatus, TaskStore, TaskUpdate};
 
 /// Session lifecycle management and orchestration.
 pub use session::{Session, SessionId, SessionManager, SessionStatus};
@@ -28,6 +29,9 @@ pub use session::{Session, SessionId, SessionManager, SessionStatus};
 /// Agent task file management and operations.
 pub use agent_tasks::AgentTasks;
 
+/// Opt-in forge (GitHub/GitLab/Forgejo) pull-request automation.
+pub use forge::ForgeConfig;
+
 /// Interactive editor integration for task content creation.
 pub use editor::{edit_content_interactive, EditorError, EDITOR_HINT};
 
@@ -38,4 +42,4 @@ pub use devshell::devshell_names;
 pub use push::{parse_push_to_remote_flag, PushHandler, PushOptions};
 
 /// Database integration for persistence.
-pub use db::DatabaseManager;
+pub use db::{DatabaseManager, SqliteTaskStore};
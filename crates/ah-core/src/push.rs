@@ -53,14 +53,16 @@ impl PushHandler {
     /// This replicates the push logic from the Ruby implementation:
     /// - Parse boolean flag or prompt interactively
     /// - Execute push operation if requested
-    pub async fn handle_push(&self, options: &PushOptions) -> Result<()> {
+    ///
+    /// Returns whether the branch was actually pushed.
+    pub async fn handle_push(&self, options: &PushOptions) -> Result<bool> {
         let should_push = self.determine_push_behavior(options).await?;
 
         if should_push {
             self.execute_push(&options.branch_name, &options.remote).await?;
         }
 
-        Ok(())
+        Ok(should_push)
     }
 
     /// Determine whether to push based on options (interactive or explicit)
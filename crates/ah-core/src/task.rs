@@ -1,9 +1,23 @@
 //! Task lifecycle management and orchestration.
 
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, RwLock};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+
+/// Number of buffered updates a subscriber can lag behind before it starts
+/// missing events. Chosen generously for a per-process task list; a
+/// subscriber that falls this far behind will observe a gap (see
+/// [`TaskManager::subscribe`]) rather than block task creation/updates.
+const UPDATE_CHANNEL_CAPACITY: usize = 256;
+
+/// Default retention window for terminal tasks, counted from the moment they
+/// enter a terminal status (see [`TaskManager::reap`]).
+const DEFAULT_RETENTION: Duration = Duration::from_secs(6 * 60 * 60);
 
 /// Unique identifier for a task.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -24,6 +38,37 @@ pub enum TaskStatus {
     Cancelled,
 }
 
+impl TaskStatus {
+    /// Whether this status is a terminal state the task will not leave.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, TaskStatus::Completed | TaskStatus::Failed | TaskStatus::Cancelled)
+    }
+
+    /// Stable, lowercase identifier for this status, used by [`TaskStore`]
+    /// implementations that persist it as a plain string column.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::Running => "running",
+            TaskStatus::Completed => "completed",
+            TaskStatus::Failed => "failed",
+            TaskStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Parse a status previously produced by [`TaskStatus::as_str`].
+    pub fn parse(s: &str) -> crate::Result<Self> {
+        match s {
+            "pending" => Ok(TaskStatus::Pending),
+            "running" => Ok(TaskStatus::Running),
+            "completed" => Ok(TaskStatus::Completed),
+            "failed" => Ok(TaskStatus::Failed),
+            "cancelled" => Ok(TaskStatus::Cancelled),
+            other => Err(crate::Error::task(format!("unknown task status {:?}", other))),
+        }
+    }
+}
+
 /// Represents a task in the AH system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -58,20 +103,197 @@ impl Task {
     }
 }
 
+/// A point-in-time notification that a task was created or changed status,
+/// as broadcast by [`TaskManager::subscribe`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskUpdate {
+    pub id: TaskId,
+    pub status: TaskStatus,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<&Task> for TaskUpdate {
+    fn from(task: &Task) -> Self {
+        Self { id: task.id, status: task.status, updated_at: task.updated_at }
+    }
+}
+
+/// A tracked task plus the bookkeeping [`TaskManager::reap`] needs to decide
+/// whether it can be dropped yet. Kept separate from [`Task`] since this
+/// bookkeeping (an [`Instant`], a dirty flag) has no business being part of
+/// the task's own serializable state.
+#[derive(Debug, Clone)]
+struct TaskEntry {
+    task: Task,
+    /// When the task entered a terminal status, if it has.
+    dropped_at: Option<Instant>,
+    /// Set whenever the task's status changes; cleared once the broadcast
+    /// channel has drained, as a proxy for "delivered to every watcher".
+    dirty: bool,
+}
+
+impl TaskEntry {
+    fn new(task: Task) -> Self {
+        Self { task, dropped_at: None, dirty: true }
+    }
+
+    fn update_status(&mut self, status: TaskStatus) {
+        self.task.update_status(status);
+        self.dirty = true;
+        if status.is_terminal() && self.dropped_at.is_none() {
+            self.dropped_at = Some(Instant::now());
+        }
+    }
+
+    /// Build an entry for a task rehydrated from a [`TaskStore`] at startup.
+    ///
+    /// A terminal task loaded this way is marked as if it had just dropped
+    /// (so it still gets one full retention window rather than vanishing on
+    /// the first [`TaskManager::reap`]) but not dirty, since there are no
+    /// watchers yet waiting on delivery of an update they've never seen.
+    fn from_loaded(task: Task) -> Self {
+        let dropped_at = task.status.is_terminal().then(Instant::now);
+        Self { task, dropped_at, dirty: false }
+    }
+}
+
+/// Persistence backend for [`TaskManager`], so task history can outlive a
+/// single process (see [`TaskManager::with_store`]).
+///
+/// `create`/`update_status` are the write-through path used as tasks change;
+/// `list` and `load_all` both return every stored task, but are kept as
+/// separate trait methods because they serve different callers: `list` is
+/// for ad-hoc inspection of a store, while `load_all` is specifically what
+/// [`TaskManager::with_store`] calls once, at construction time, to
+/// rehydrate its in-memory state.
+#[async_trait]
+pub trait TaskStore: std::fmt::Debug + Send + Sync {
+    /// Persist a newly created task.
+    async fn create(&self, task: &Task) -> crate::Result<()>;
+
+    /// Fetch a single task by id.
+    async fn get(&self, id: TaskId) -> crate::Result<Option<Task>>;
+
+    /// Persist a task's new status and `updated_at`.
+    async fn update_status(
+        &self,
+        id: TaskId,
+        status: TaskStatus,
+        updated_at: chrono::DateTime<chrono::Utc>,
+    ) -> crate::Result<()>;
+
+    /// List every task currently in the store.
+    async fn list(&self) -> crate::Result<Vec<Task>>;
+
+    /// Load every task at startup, used to rehydrate a [`TaskManager`].
+    async fn load_all(&self) -> crate::Result<Vec<Task>>;
+}
+
+/// In-memory [`TaskStore`], used internally by [`TaskManager::new`] so a
+/// manager always has somewhere to write tasks through to, without requiring
+/// every caller to provide a durable backend.
+#[derive(Debug, Default)]
+struct InMemoryTaskStore {
+    tasks: RwLock<HashMap<TaskId, Task>>,
+}
+
+#[async_trait]
+impl TaskStore for InMemoryTaskStore {
+    async fn create(&self, task: &Task) -> crate::Result<()> {
+        self.tasks.write().await.insert(task.id, task.clone());
+        Ok(())
+    }
+
+    async fn get(&self, id: TaskId) -> crate::Result<Option<Task>> {
+        Ok(self.tasks.read().await.get(&id).cloned())
+    }
+
+    async fn update_status(
+        &self,
+        id: TaskId,
+        status: TaskStatus,
+        updated_at: chrono::DateTime<chrono::Utc>,
+    ) -> crate::Result<()> {
+        let mut tasks = self.tasks.write().await;
+        let task =
+            tasks.get_mut(&id).ok_or_else(|| crate::Error::task(format!("Task {} not found", id.0)))?;
+        task.status = status;
+        task.updated_at = updated_at;
+        Ok(())
+    }
+
+    async fn list(&self) -> crate::Result<Vec<Task>> {
+        Ok(self.tasks.read().await.values().cloned().collect())
+    }
+
+    async fn load_all(&self) -> crate::Result<Vec<Task>> {
+        self.list().await
+    }
+}
+
 /// Manages the lifecycle of tasks in the system.
 #[derive(Debug, Clone)]
 pub struct TaskManager {
-    tasks: Arc<RwLock<HashMap<TaskId, Task>>>,
+    tasks: Arc<RwLock<HashMap<TaskId, TaskEntry>>>,
     next_id: Arc<RwLock<TaskId>>,
+    updates: broadcast::Sender<TaskUpdate>,
+    retention: Duration,
+    store: Arc<dyn TaskStore>,
 }
 
 impl TaskManager {
-    /// Create a new task manager.
+    /// Create a new, purely in-memory task manager with the default
+    /// retention window (6h) for terminal tasks.
     pub fn new() -> Self {
+        Self::with_retention(DEFAULT_RETENTION)
+    }
+
+    /// Create a new, purely in-memory task manager that retains terminal
+    /// tasks for `retention` after they enter a terminal status, and beyond
+    /// that for as long as a live watcher has not yet received their last
+    /// update (see [`TaskManager::reap`]).
+    pub fn with_retention(retention: Duration) -> Self {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
         Self {
             tasks: Arc::new(RwLock::new(HashMap::new())),
             next_id: Arc::new(RwLock::new(TaskId(1))),
+            updates,
+            retention,
+            store: Arc::new(InMemoryTaskStore::default()),
+        }
+    }
+
+    /// Create a task manager backed by `store`, rehydrating the next-id
+    /// counter and every task it holds on construction, so long-running and
+    /// scheduled tasks survive process restarts. Uses the default retention
+    /// window (6h) for terminal tasks.
+    pub async fn with_store(store: Arc<dyn TaskStore>) -> crate::Result<Self> {
+        Self::with_store_and_retention(store, DEFAULT_RETENTION).await
+    }
+
+    /// Like [`TaskManager::with_store`], with an explicit retention window.
+    pub async fn with_store_and_retention(
+        store: Arc<dyn TaskStore>,
+        retention: Duration,
+    ) -> crate::Result<Self> {
+        let (updates, _) = broadcast::channel(UPDATE_CHANNEL_CAPACITY);
+
+        let mut next_id = TaskId(1);
+        let mut tasks = HashMap::new();
+        for task in store.load_all().await? {
+            if task.id.0 >= next_id.0 {
+                next_id = TaskId(task.id.0 + 1);
+            }
+            tasks.insert(task.id, TaskEntry::from_loaded(task));
         }
+
+        Ok(Self {
+            tasks: Arc::new(RwLock::new(tasks)),
+            next_id: Arc::new(RwLock::new(next_id)),
+            updates,
+            retention,
+            store,
+        })
     }
 
     /// Create a new task and add it to the manager.
@@ -81,21 +303,26 @@ impl TaskManager {
         next_id.0 += 1;
 
         let task = Task::new(task_id, name, description);
-        self.tasks.write().await.insert(task_id, task);
+        self.store.create(&task).await?;
+        let update = TaskUpdate::from(&task);
+        self.tasks.write().await.insert(task_id, TaskEntry::new(task));
+        let _ = self.updates.send(update);
 
         Ok(task_id)
     }
 
     /// Get a task by its ID.
     pub async fn get_task(&self, id: TaskId) -> crate::Result<Option<Task>> {
-        Ok(self.tasks.read().await.get(&id).cloned())
+        Ok(self.tasks.read().await.get(&id).map(|entry| entry.task.clone()))
     }
 
     /// Update the status of a task.
     pub async fn update_task_status(&self, id: TaskId, status: TaskStatus) -> crate::Result<()> {
         let mut tasks = self.tasks.write().await;
-        if let Some(task) = tasks.get_mut(&id) {
-            task.update_status(status);
+        if let Some(entry) = tasks.get_mut(&id) {
+            entry.update_status(status);
+            self.store.update_status(id, status, entry.task.updated_at).await?;
+            let _ = self.updates.send(TaskUpdate::from(&entry.task));
             Ok(())
         } else {
             Err(crate::Error::task(format!("Task {} not found", id.0)))
@@ -104,7 +331,80 @@ impl TaskManager {
 
     /// List all tasks.
     pub async fn list_tasks(&self) -> crate::Result<Vec<Task>> {
-        Ok(self.tasks.read().await.values().cloned().collect())
+        Ok(self.tasks.read().await.values().map(|entry| entry.task.clone()).collect())
+    }
+
+    /// Reap terminal tasks whose retention window has elapsed.
+    ///
+    /// A non-terminal (`Pending`/`Running`) task is always retained. A
+    /// terminal task is retained while `(is_dirty && has_watchers) ||
+    /// dropped_for <= retention`: `has_watchers` is true while any
+    /// [`TaskManager::subscribe`] stream is still live, and `is_dirty`
+    /// approximates "the terminal update may not have reached every watcher
+    /// yet" by tracking whether the broadcast channel has fully drained
+    /// since the task last changed. Returns the number of tasks removed.
+    pub async fn reap(&self) -> usize {
+        let has_watchers = self.updates.receiver_count() > 0;
+        let channel_drained = self.updates.len() == 0;
+        let now = Instant::now();
+
+        let mut tasks = self.tasks.write().await;
+        if channel_drained {
+            for entry in tasks.values_mut() {
+                entry.dirty = false;
+            }
+        }
+
+        let before = tasks.len();
+        tasks.retain(|_, entry| match entry.dropped_at {
+            None => true,
+            Some(dropped_at) => {
+                (entry.dirty && has_watchers) || now.duration_since(dropped_at) <= self.retention
+            }
+        });
+        before - tasks.len()
+    }
+
+    /// Spawn a background task that calls [`TaskManager::reap`] on `interval`
+    /// until the returned handle is dropped or aborted.
+    pub fn spawn_retention_sweeper(&self, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                manager.reap().await;
+            }
+        })
+    }
+
+    /// Subscribe to task lifecycle updates.
+    ///
+    /// The returned stream first yields a synthetic [`TaskUpdate`] for every
+    /// task that exists at subscription time (so a late joiner starts from a
+    /// consistent view of the world), then continues with the live stream of
+    /// updates as tasks are created or change status.
+    ///
+    /// The live portion is backed by a bounded broadcast channel
+    /// ([`UPDATE_CHANNEL_CAPACITY`]); a subscriber that falls too far behind
+    /// will observe a gap rather than block task creation/updates. Lagged
+    /// receivers (see [`BroadcastStreamRecvError::Lagged`]) simply skip the
+    /// missed updates rather than erroring the stream — callers only ever
+    /// see successfully delivered `TaskUpdate`s.
+    pub async fn subscribe(&self) -> impl Stream<Item = TaskUpdate> {
+        // Subscribe before taking the snapshot: a task created between the
+        // two would otherwise be missing from both, breaking the "late
+        // joiners see a consistent view" guarantee. Subscribing first only
+        // risks a benign duplicate `TaskUpdate` (once in the snapshot, once
+        // live), which is harmless for callers that treat updates as
+        // idempotent state, not a missed one.
+        let receiver = self.updates.subscribe();
+        let snapshot: Vec<TaskUpdate> =
+            self.tasks.read().await.values().map(|entry| TaskUpdate::from(&entry.task)).collect();
+        let live =
+            BroadcastStream::new(receiver).filter_map(|item: Result<TaskUpdate, BroadcastStreamRecvError>| item.ok());
+
+        tokio_stream::iter(snapshot).chain(live)
     }
 }
 
@@ -113,3 +413,71 @@ impl Default for TaskManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn non_terminal_tasks_are_never_reaped() {
+        let manager = TaskManager::with_retention(Duration::ZERO);
+        let id = manager.create_task("t".into(), "d".into()).await.unwrap();
+
+        assert_eq!(manager.reap().await, 0);
+        assert!(manager.get_task(id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn terminal_task_survives_while_watcher_has_unsent_updates() {
+        let manager = TaskManager::with_retention(Duration::ZERO);
+        let id = manager.create_task("t".into(), "d".into()).await.unwrap();
+
+        // Subscribe but never poll the stream, so the terminal update stays
+        // buffered in the broadcast channel (`updates.len() > 0`).
+        let watcher = manager.subscribe().await;
+
+        manager.update_task_status(id, TaskStatus::Completed).await.unwrap();
+        assert_eq!(manager.reap().await, 0, "dirty task with a live watcher must be retained");
+        assert!(manager.get_task(id).await.unwrap().is_some());
+
+        drop(watcher);
+    }
+
+    #[tokio::test]
+    async fn terminal_task_is_reaped_once_delivered_and_retention_elapses() {
+        let manager = TaskManager::with_retention(Duration::ZERO);
+        let id = manager.create_task("t".into(), "d".into()).await.unwrap();
+
+        let mut watcher = Box::pin(manager.subscribe().await);
+        manager.update_task_status(id, TaskStatus::Completed).await.unwrap();
+
+        // Drain the snapshot + live update so the channel reports empty.
+        while watcher.next().await.is_some() {
+            if manager.updates.len() == 0 {
+                break;
+            }
+        }
+
+        assert_eq!(manager.reap().await, 1);
+        assert!(manager.get_task(id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn with_store_rehydrates_tasks_and_next_id() {
+        let store: Arc<dyn TaskStore> = Arc::new(InMemoryTaskStore::default());
+
+        let manager = TaskManager::with_store(store.clone()).await.unwrap();
+        let id = manager.create_task("t".into(), "d".into()).await.unwrap();
+        manager.update_task_status(id, TaskStatus::Running).await.unwrap();
+
+        // Simulate a process restart: a fresh manager over the same store
+        // should see the task with its latest status, and hand out ids
+        // starting after the last one already stored.
+        let restarted = TaskManager::with_store(store).await.unwrap();
+        let task = restarted.get_task(id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Running);
+
+        let next_id = restarted.create_task("t2".into(), "d2".into()).await.unwrap();
+        assert_eq!(next_id.0, id.0 + 1);
+    }
+}
@@ -0,0 +1,144 @@
+//! Forgejo implementation of the [`ah_forge::Forge`] trait.
+//!
+//! Forgejo instances are self-hosted, so unlike GitHub/GitLab there's no
+//! fixed hostname to detect them from — callers must know they're talking to
+//! a Forgejo instance and supply its base URL explicitly.
+
+use ah_forge::{Error, Forge, ForgeKind, PullRequest, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+const TOKEN_ENV_VAR: &str = "FORGEJO_TOKEN";
+
+/// Talks to a self-hosted Forgejo instance's Gitea-compatible REST API.
+#[derive(Debug)]
+pub struct ForgejoForge {
+    client: Client,
+    base_url: String,
+    token: String,
+    owner: String,
+    repo: String,
+}
+
+impl ForgejoForge {
+    /// Build a client for `owner/repo` on `base_url`, looking the token up
+    /// from `FORGEJO_TOKEN`.
+    pub fn from_env(
+        base_url: impl Into<String>,
+        owner: impl Into<String>,
+        repo: impl Into<String>,
+    ) -> Result<Self> {
+        let token = std::env::var(TOKEN_ENV_VAR).map_err(|_| Error::MissingToken {
+            forge: "Forgejo",
+            env_var: TOKEN_ENV_VAR,
+        })?;
+        Ok(Self::new(base_url, token, owner, repo))
+    }
+
+    /// Build a client for `owner/repo` on `base_url` with an explicit token.
+    pub fn new(
+        base_url: impl Into<String>,
+        token: impl Into<String>,
+        owner: impl Into<String>,
+        repo: impl Into<String>,
+    ) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("ah-forge-forgejo/1.0")
+                .build()
+                .expect("Failed to create HTTP client"),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PullRequestResponse {
+    number: u64,
+    html_url: String,
+}
+
+#[async_trait]
+impl Forge for ForgejoForge {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Forgejo
+    }
+
+    async fn push_branch(&self, head: &str, remote: &str) -> Result<()> {
+        let _ = (head, remote);
+        Ok(())
+    }
+
+    async fn open_pull_request(
+        &self,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest> {
+        let url = format!(
+            "{}/api/v1/repos/{}/{}/pulls",
+            self.base_url, self.owner, self.repo
+        );
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .json(&json!({
+                // Forgejo has no draft flag; a `WIP:` title prefix marks the
+                // pull request as a work-in-progress draft.
+                "title": format!("WIP: {title}"),
+                "body": body,
+                "head": head,
+                "base": base,
+            }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(Error::Api {
+                forge: "Forgejo",
+                status: status.as_u16(),
+                message,
+            });
+        }
+
+        let pr: PullRequestResponse = response.json().await?;
+        Ok(PullRequest {
+            number: pr.number,
+            url: pr.html_url,
+        })
+    }
+
+    async fn update_pull_request(&self, pr: &PullRequest, body: &str) -> Result<()> {
+        let url = format!(
+            "{}/api/v1/repos/{}/{}/pulls/{}",
+            self.base_url, self.owner, self.repo, pr.number
+        );
+        let response = self
+            .client
+            .patch(&url)
+            .bearer_auth(&self.token)
+            .json(&json!({ "body": body }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(Error::Api {
+                forge: "Forgejo",
+                status: status.as_u16(),
+                message,
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! GitHub implementation of the [`ah_forge::Forge`] trait.
+
+use ah_forge::{Error, Forge, ForgeKind, PullRequest, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+const API_BASE: &str = "https://api.github.com";
+const TOKEN_ENV_VAR: &str = "GITHUB_TOKEN";
+
+/// Talks to a `github.com` repository's REST API.
+#[derive(Debug)]
+pub struct GitHubForge {
+    client: Client,
+    token: String,
+    owner: String,
+    repo: String,
+}
+
+impl GitHubForge {
+    /// Build a client for `owner/repo`, looking the token up from `GITHUB_TOKEN`.
+    pub fn from_env(owner: impl Into<String>, repo: impl Into<String>) -> Result<Self> {
+        let token = std::env::var(TOKEN_ENV_VAR).map_err(|_| Error::MissingToken {
+            forge: "GitHub",
+            env_var: TOKEN_ENV_VAR,
+        })?;
+        Ok(Self::new(token, owner, repo))
+    }
+
+    /// Build a client for `owner/repo` with an explicit token.
+    pub fn new(token: impl Into<String>, owner: impl Into<String>, repo: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("ah-forge-github/1.0")
+                .build()
+                .expect("Failed to create HTTP client"),
+            token: token.into(),
+            owner: owner.into(),
+            repo: repo.into(),
+        }
+    }
+
+    async fn handle_response<T: for<'de> Deserialize<'de>>(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<T> {
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(Error::Api {
+                forge: "GitHub",
+                status: status.as_u16(),
+                message,
+            });
+        }
+        Ok(response.json::<T>().await?)
+    }
+}
+
+#[derive(Deserialize)]
+struct PullRequestResponse {
+    number: u64,
+    html_url: String,
+}
+
+#[async_trait]
+impl Forge for GitHubForge {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::GitHub
+    }
+
+    async fn push_branch(&self, head: &str, remote: &str) -> Result<()> {
+        // Pushing is a local `git` operation, not a GitHub API call; callers
+        // push via `VcsRepo::push_current_branch` before opening the PR.
+        let _ = (head, remote);
+        Ok(())
+    }
+
+    async fn open_pull_request(
+        &self,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest> {
+        let url = format!("{API_BASE}/repos/{}/{}/pulls", self.owner, self.repo);
+        let response = self
+            .client
+            .post(&url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .json(&json!({
+                "title": title,
+                "body": body,
+                "head": head,
+                "base": base,
+                "draft": true,
+            }))
+            .send()
+            .await?;
+
+        let pr: PullRequestResponse = self.handle_response(response).await?;
+        Ok(PullRequest {
+            number: pr.number,
+            url: pr.html_url,
+        })
+    }
+
+    async fn update_pull_request(&self, pr: &PullRequest, body: &str) -> Result<()> {
+        let url = format!(
+            "{API_BASE}/repos/{}/{}/pulls/{}",
+            self.owner, self.repo, pr.number
+        );
+        let response = self
+            .client
+            .patch(&url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .json(&json!({ "body": body }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(Error::Api {
+                forge: "GitHub",
+                status: status.as_u16(),
+                message,
+            });
+        }
+        Ok(())
+    }
+}
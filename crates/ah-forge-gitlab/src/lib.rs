@@ -0,0 +1,139 @@
+//! GitLab implementation of the [`ah_forge::Forge`] trait.
+
+use ah_forge::{Error, Forge, ForgeKind, PullRequest, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+const API_BASE: &str = "https://gitlab.com/api/v4";
+const TOKEN_ENV_VAR: &str = "GITLAB_TOKEN";
+
+/// Talks to a `gitlab.com` project's REST API. GitLab calls pull requests
+/// "merge requests", but we still return [`PullRequest`] to keep the trait
+/// forge-agnostic.
+#[derive(Debug)]
+pub struct GitLabForge {
+    client: Client,
+    token: String,
+    project_path: String,
+}
+
+impl GitLabForge {
+    /// Build a client for `owner/repo`, looking the token up from `GITLAB_TOKEN`.
+    pub fn from_env(owner: impl Into<String>, repo: impl Into<String>) -> Result<Self> {
+        let token = std::env::var(TOKEN_ENV_VAR).map_err(|_| Error::MissingToken {
+            forge: "GitLab",
+            env_var: TOKEN_ENV_VAR,
+        })?;
+        Ok(Self::new(token, owner, repo))
+    }
+
+    /// Build a client for `owner/repo` with an explicit token.
+    pub fn new(token: impl Into<String>, owner: impl Into<String>, repo: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .user_agent("ah-forge-gitlab/1.0")
+                .build()
+                .expect("Failed to create HTTP client"),
+            token: token.into(),
+            project_path: format!("{}/{}", owner.into(), repo.into()),
+        }
+    }
+
+    fn project_id(&self) -> String {
+        urlencoding_replace_slashes(&self.project_path)
+    }
+}
+
+/// GitLab's project-by-path API expects the `owner/repo` path with `/`
+/// percent-encoded as `%2F`; full URL-encoding isn't needed since project
+/// paths don't contain other reserved characters.
+fn urlencoding_replace_slashes(path: &str) -> String {
+    path.replace('/', "%2F")
+}
+
+#[derive(Deserialize)]
+struct MergeRequestResponse {
+    iid: u64,
+    web_url: String,
+}
+
+#[async_trait]
+impl Forge for GitLabForge {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::GitLab
+    }
+
+    async fn push_branch(&self, head: &str, remote: &str) -> Result<()> {
+        let _ = (head, remote);
+        Ok(())
+    }
+
+    async fn open_pull_request(
+        &self,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest> {
+        let url = format!(
+            "{API_BASE}/projects/{}/merge_requests",
+            self.project_id()
+        );
+        let response = self
+            .client
+            .post(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&json!({
+                "title": format!("Draft: {title}"),
+                "description": body,
+                "source_branch": head,
+                "target_branch": base,
+            }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(Error::Api {
+                forge: "GitLab",
+                status: status.as_u16(),
+                message,
+            });
+        }
+
+        let mr: MergeRequestResponse = response.json().await?;
+        Ok(PullRequest {
+            number: mr.iid,
+            url: mr.web_url,
+        })
+    }
+
+    async fn update_pull_request(&self, pr: &PullRequest, body: &str) -> Result<()> {
+        let url = format!(
+            "{API_BASE}/projects/{}/merge_requests/{}",
+            self.project_id(),
+            pr.number
+        );
+        let response = self
+            .client
+            .put(&url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .json(&json!({ "description": body }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let message = response.text().await.unwrap_or_default();
+            return Err(Error::Api {
+                forge: "GitLab",
+                status: status.as_u16(),
+                message,
+            });
+        }
+        Ok(())
+    }
+}
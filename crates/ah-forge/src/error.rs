@@ -0,0 +1,32 @@
+//! Error types for forge operations.
+
+use thiserror::Error;
+
+/// Errors that can occur when talking to a code-forge (GitHub, GitLab, Forgejo, ...).
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The `origin` remote URL doesn't match any known forge.
+    #[error("could not detect a forge from remote URL: {0}")]
+    UnknownForge(String),
+
+    /// No API token was found via environment/credential lookup.
+    #[error("no {forge} API token found; set {env_var}")]
+    MissingToken { forge: &'static str, env_var: &'static str },
+
+    /// The forge's API returned an error response.
+    #[error("{forge} API error ({status}): {message}")]
+    Api {
+        forge: &'static str,
+        status: u16,
+        message: String,
+    },
+
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// Result type alias for forge operations.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,146 @@
+//! Code-forge abstractions for Agents Workflow.
+//!
+//! This crate provides a pluggable [`Forge`] trait for pushing an agent task
+//! branch and opening/updating the pull request that tracks it, so
+//! `ah-core::agent_tasks` doesn't need to know whether `origin` is GitHub,
+//! GitLab, or Forgejo. Platform crates (`ah-forge-github`, `ah-forge-gitlab`,
+//! `ah-forge-forgejo`) provide the concrete implementations; this crate only
+//! defines the trait and detects which one applies to a given remote URL.
+
+use async_trait::async_trait;
+
+pub mod error;
+
+/// Result type for forge operations.
+pub use error::{Error, Result};
+
+/// Which forge a remote URL belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+    Forgejo,
+}
+
+impl ForgeKind {
+    /// Human-readable name, used in error messages.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "GitHub",
+            ForgeKind::GitLab => "GitLab",
+            ForgeKind::Forgejo => "Forgejo",
+        }
+    }
+}
+
+/// A pull (or merge) request opened on a forge.
+#[derive(Debug, Clone)]
+pub struct PullRequest {
+    /// Forge-assigned PR/MR number.
+    pub number: u64,
+    /// Web URL for the PR/MR.
+    pub url: String,
+}
+
+/// Core trait implemented by each forge backend.
+///
+/// `head`/`base` are branch names; implementations are constructed with the
+/// owner/repo slug (see [`parse_owner_repo`]) and target it for every call.
+#[async_trait]
+pub trait Forge: std::fmt::Debug + Send + Sync {
+    /// Which forge this implementation talks to.
+    fn kind(&self) -> ForgeKind;
+
+    /// Push `head` to the forge's remote. The GitHub, GitLab and Forgejo
+    /// backends do nothing here and return `Ok(())`: the branch is pushed
+    /// with `git` (`VcsRepo::push_current_branch`) before a PR is opened.
+    async fn push_branch(&self, head: &str, remote: &str) -> Result<()>;
+
+    /// Open a new (draft) pull request from `head` onto `base`.
+    async fn open_pull_request(
+        &self,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest>;
+
+    /// Replace the description of an already-open pull request.
+    async fn update_pull_request(&self, pr: &PullRequest, body: &str) -> Result<()>;
+}
+
+/// Detect which forge an `origin`-style remote URL belongs to by hostname.
+///
+/// Self-hosted Forgejo instances have no fixed hostname, so they can't be
+/// detected this way; callers that need Forgejo support must construct a
+/// [`Forge`] backend directly rather than going through this helper.
+pub fn detect_forge_kind(remote_url: &str) -> Option<ForgeKind> {
+    let host = remote_url
+        .trim_start_matches("git@")
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split(['/', ':'])
+        .next()?;
+
+    if host.eq_ignore_ascii_case("github.com") {
+        Some(ForgeKind::GitHub)
+    } else if host.eq_ignore_ascii_case("gitlab.com") {
+        Some(ForgeKind::GitLab)
+    } else {
+        None
+    }
+}
+
+/// Split a `github.com`/`gitlab.com`-style remote URL into `(owner, repo)`,
+/// stripping a trailing `.git` if present. Shared by the `ah-forge-*` backend
+/// crates so each doesn't reimplement its own URL parsing.
+pub fn parse_owner_repo(remote_url: &str) -> Option<(String, String)> {
+    let without_scheme = remote_url
+        .trim_start_matches("git@")
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+
+    let path = without_scheme.splitn(2, [':', '/']).nth(1)?;
+    let path = path.trim_end_matches(".git").trim_end_matches('/');
+
+    let mut parts = path.rsplitn(2, '/');
+    let repo = parts.next()?;
+    let owner = parts.next()?;
+    Some((owner.to_string(), repo.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_github_from_ssh_and_https_urls() {
+        assert_eq!(detect_forge_kind("git@github.com:owner/repo.git"), Some(ForgeKind::GitHub));
+        assert_eq!(
+            detect_forge_kind("https://github.com/owner/repo.git"),
+            Some(ForgeKind::GitHub)
+        );
+    }
+
+    #[test]
+    fn detects_gitlab() {
+        assert_eq!(detect_forge_kind("git@gitlab.com:owner/repo.git"), Some(ForgeKind::GitLab));
+    }
+
+    #[test]
+    fn unknown_host_is_undetected() {
+        assert_eq!(detect_forge_kind("git@git.example.com:owner/repo.git"), None);
+    }
+
+    #[test]
+    fn parses_owner_repo_from_ssh_and_https_urls() {
+        assert_eq!(
+            parse_owner_repo("git@github.com:owner/repo.git"),
+            Some(("owner".to_string(), "repo".to_string()))
+        );
+        assert_eq!(
+            parse_owner_repo("https://gitlab.com/owner/repo"),
+            Some(("owner".to_string(), "repo".to_string()))
+        );
+    }
+}
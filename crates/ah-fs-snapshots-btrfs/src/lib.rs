@@ -5,10 +5,91 @@ use ah_fs_snapshots_traits::{
     SnapshotRef, WorkingCopyMode,
 };
 use std::collections::HashMap;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
 
+/// Check whether this process could hold a private mount namespace without root, by
+/// forking a throwaway child and having it attempt `unshare(CLONE_NEWUSER)`. Mirrors
+/// the `user_namespaces_available` probe in the debugging-enforcement test orchestrator.
+fn unprivileged_userns_available() -> bool {
+    use nix::sched::{unshare, CloneFlags};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+
+    match unsafe { fork() } {
+        Ok(ForkResult::Child) => {
+            let ok = unshare(CloneFlags::CLONE_NEWUSER).is_ok();
+            std::process::exit(if ok { 0 } else { 1 });
+        }
+        Ok(ForkResult::Parent { child }) => {
+            matches!(waitpid(child, None), Ok(WaitStatus::Exited(_, 0)))
+        }
+        Err(_) => false,
+    }
+}
+
+/// Enter a private mount namespace for the current process, unshare-ing a user
+/// namespace first (with a root-in-namespace uid/gid mapping, same scheme as
+/// `sandbox-core`) when not already running as root, so Worktree mode works
+/// unprivileged.
+fn enter_private_mount_namespace() -> Result<()> {
+    use nix::mount::{mount, MsFlags};
+    use nix::sched::{unshare, CloneFlags};
+    use nix::unistd::{getgid, getuid, setresgid, setresuid, Gid, Uid};
+
+    if !nix::unistd::geteuid().is_root() {
+        unshare(CloneFlags::CLONE_NEWUSER).map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!(
+                "Failed to unshare user namespace for Worktree mode: {}",
+                e
+            ))
+        })?;
+
+        std::fs::write("/proc/self/setgroups", "deny").map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!("Failed to deny setgroups: {}", e))
+        })?;
+        std::fs::write("/proc/self/uid_map", format!("0 {} 1", getuid().as_raw())).map_err(
+            |e| ah_fs_snapshots_traits::Error::provider(format!("Failed to write uid_map: {}", e)),
+        )?;
+        std::fs::write("/proc/self/gid_map", format!("0 {} 1", getgid().as_raw())).map_err(
+            |e| ah_fs_snapshots_traits::Error::provider(format!("Failed to write gid_map: {}", e)),
+        )?;
+        setresuid(Uid::from_raw(0), Uid::from_raw(0), Uid::from_raw(0)).map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!("Failed to setresuid: {}", e))
+        })?;
+        setresgid(Gid::from_raw(0), Gid::from_raw(0), Gid::from_raw(0)).map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!("Failed to setresgid: {}", e))
+        })?;
+    }
+
+    unshare(CloneFlags::CLONE_NEWNS).map_err(|e| {
+        ah_fs_snapshots_traits::Error::provider(format!(
+            "Failed to unshare mount namespace for Worktree mode: {}",
+            e
+        ))
+    })?;
+
+    // Make our mount tree private so the bind mount we're about to add doesn't
+    // propagate back out to the host's view of the filesystem.
+    mount(
+        None::<&str>,
+        "/",
+        None::<&str>,
+        MsFlags::MS_REC | MsFlags::MS_PRIVATE,
+        None::<&str>,
+    )
+    .map_err(|e| {
+        ah_fs_snapshots_traits::Error::provider(format!(
+            "Failed to mark mount tree private: {}",
+            e
+        ))
+    })?;
+
+    Ok(())
+}
+
 /// Btrfs snapshot provider implementation.
 #[derive(Default)]
 pub struct BtrfsProvider;
@@ -100,6 +181,55 @@ impl BtrfsProvider {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     }
 
+    /// Implement Worktree mode as an overlay-in-place: a writable subvolume snapshot
+    /// is bind-mounted directly over the original repo path inside a private mount
+    /// namespace, so `exec_path` equals `repo` and nothing outside this process (and
+    /// whatever it forks afterward) ever sees the mount.
+    fn prepare_worktree_overlay(&self, repo: &Path) -> Result<PreparedWorkspace> {
+        let unique_id = ah_fs_snapshots_traits::generate_unique_id();
+        let subvolume_path = repo.with_file_name(format!("ah_worktree_{}", unique_id));
+
+        // Writable subvolume snapshot (no -r) that will receive all writes.
+        self.execute_btrfs_command(&[
+            "subvolume",
+            "snapshot",
+            repo.to_str().unwrap(),
+            subvolume_path.to_str().unwrap(),
+        ])?;
+
+        enter_private_mount_namespace().map_err(|e| {
+            let _ = self.execute_btrfs_command(&["subvolume", "delete", subvolume_path.to_str().unwrap()]);
+            e
+        })?;
+
+        nix::mount::mount(
+            Some(subvolume_path.as_path()),
+            repo,
+            None::<&str>,
+            nix::mount::MsFlags::MS_BIND,
+            None::<&str>,
+        )
+        .map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!(
+                "Failed to bind-mount writable snapshot {} over {}: {}",
+                subvolume_path.display(),
+                repo.display(),
+                e
+            ))
+        })?;
+
+        Ok(PreparedWorkspace {
+            exec_path: repo.to_path_buf(),
+            working_copy: WorkingCopyMode::Worktree,
+            provider: self.kind(),
+            cleanup_token: format!(
+                "btrfs:worktree:{}:{}",
+                repo.display(),
+                subvolume_path.display()
+            ),
+        })
+    }
+
     /// Generate a unique identifier for Btrfs resources.
     fn generate_unique_id(&self) -> String {
         use std::time::{SystemTime, UNIX_EPOCH};
@@ -109,6 +239,176 @@ impl BtrfsProvider {
             .as_nanos();
         format!("ah_{}_{}", std::process::id(), timestamp)
     }
+
+    /// Stream a read-only subvolume snapshot out as a `btrfs send` byte stream.
+    ///
+    /// When `parent` is given, sends an incremental stream (`btrfs send -p <parent>`)
+    /// containing only the blocks that changed since that snapshot, which the
+    /// receiving end must already hold at the same relative path. This is not yet
+    /// part of the `FsSnapshotProvider` trait (which lives in `ah-fs-snapshots-traits`,
+    /// not present in this checkout) - it should be added there as a default-less
+    /// trait method once that crate is available.
+    pub fn export_snapshot(
+        &self,
+        snap: &SnapshotRef,
+        parent: Option<&SnapshotRef>,
+        writer: &mut dyn Write,
+    ) -> Result<()> {
+        let snapshot_path = snap.meta.get("snapshot_path").ok_or_else(|| {
+            ah_fs_snapshots_traits::Error::provider("Snapshot missing snapshot_path metadata")
+        })?;
+
+        let mut args = vec!["send".to_string()];
+        if let Some(parent) = parent {
+            let parent_path = parent.meta.get("snapshot_path").ok_or_else(|| {
+                ah_fs_snapshots_traits::Error::provider(
+                    "Parent snapshot missing snapshot_path metadata required for incremental send",
+                )
+            })?;
+            args.push("-p".to_string());
+            args.push(parent_path.clone());
+        }
+        args.push(snapshot_path.clone());
+
+        let mut child = std::process::Command::new("btrfs")
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                ah_fs_snapshots_traits::Error::provider(format!("Failed to spawn btrfs send: {}", e))
+            })?;
+
+        let mut child_stdout = child.stdout.take().expect("btrfs send stdout was piped");
+        let mut child_stderr = child.stderr.take().expect("btrfs send stderr was piped");
+        let stderr_reader = std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = child_stderr.read_to_string(&mut buf);
+            buf
+        });
+
+        let copy_result = std::io::copy(&mut child_stdout, writer);
+        let stderr_output = stderr_reader.join().unwrap_or_default();
+        let status = child.wait().map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!("Failed to reap btrfs send: {}", e))
+        })?;
+
+        copy_result.map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!(
+                "Failed to stream btrfs send output: {}",
+                e
+            ))
+        })?;
+
+        if !status.success() {
+            return Err(ah_fs_snapshots_traits::Error::provider(format!(
+                "btrfs send failed: {}",
+                stderr_output
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Receive a `btrfs send` byte stream produced by [`Self::export_snapshot`] into
+    /// `dest_dir`, reconstructing a [`SnapshotRef`] with the same `meta` layout
+    /// (`source_path`, `snapshot_path`, `timestamp`) that `snapshot_now` produces.
+    ///
+    /// When `parent` is given, its id is recorded as `parent_id` in the returned
+    /// snapshot's metadata so a chain of incremental sends can be validated (e.g. that
+    /// each link's parent is already present locally) before attempting to receive it.
+    pub fn import_snapshot(
+        &self,
+        reader: &mut dyn Read,
+        dest_dir: &Path,
+        parent: Option<&SnapshotRef>,
+        label: Option<&str>,
+    ) -> Result<SnapshotRef> {
+        let mut child = std::process::Command::new("btrfs")
+            .args(["receive", "-f", "-", "-v", dest_dir.to_str().unwrap()])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                ah_fs_snapshots_traits::Error::provider(format!(
+                    "Failed to spawn btrfs receive: {}",
+                    e
+                ))
+            })?;
+
+        let mut child_stdin = child.stdin.take().expect("btrfs receive stdin was piped");
+        let mut child_stdout = child.stdout.take().expect("btrfs receive stdout was piped");
+        let mut child_stderr = child.stderr.take().expect("btrfs receive stderr was piped");
+
+        let stderr_reader = std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = child_stderr.read_to_string(&mut buf);
+            buf
+        });
+        let stdout_reader = std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = child_stdout.read_to_string(&mut buf);
+            buf
+        });
+
+        let copy_result = std::io::copy(reader, &mut child_stdin);
+        // Drop stdin so the child sees EOF and finishes once the stream is exhausted.
+        drop(child_stdin);
+
+        let stdout_output = stdout_reader.join().unwrap_or_default();
+        let stderr_output = stderr_reader.join().unwrap_or_default();
+        let status = child.wait().map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!("Failed to reap btrfs receive: {}", e))
+        })?;
+
+        copy_result.map_err(|e| {
+            ah_fs_snapshots_traits::Error::provider(format!(
+                "Failed to stream btrfs receive input: {}",
+                e
+            ))
+        })?;
+
+        if !status.success() {
+            return Err(ah_fs_snapshots_traits::Error::provider(format!(
+                "btrfs receive failed: {}",
+                stderr_output
+            )));
+        }
+
+        // `btrfs receive -v` reports the subvolume it created/updated as a line like
+        // `At subvol <name>` (full send) or `At snapshot <name>` (incremental).
+        let subvol_name = stdout_output
+            .lines()
+            .find_map(|line| {
+                line.strip_prefix("At subvol ").or_else(|| line.strip_prefix("At snapshot "))
+            })
+            .ok_or_else(|| {
+                ah_fs_snapshots_traits::Error::provider(
+                    "Could not determine received subvolume name from btrfs receive output",
+                )
+            })?
+            .trim();
+        let snapshot_path = dest_dir.join(subvol_name);
+
+        let mut meta = HashMap::new();
+        meta.insert("source_path".to_string(), dest_dir.to_string_lossy().to_string());
+        meta.insert(
+            "snapshot_path".to_string(),
+            snapshot_path.to_string_lossy().to_string(),
+        );
+        meta.insert("timestamp".to_string(), chrono::Utc::now().to_rfc3339());
+        if let Some(parent) = parent {
+            meta.insert("parent_id".to_string(), parent.id.clone());
+        }
+
+        Ok(SnapshotRef {
+            id: format!("btrfs_snapshot_{}", self.generate_unique_id()),
+            label: label.map(|s| s.to_string()),
+            provider: self.kind(),
+            meta,
+        })
+    }
 }
 
 impl FsSnapshotProvider for BtrfsProvider {
@@ -128,12 +428,28 @@ impl FsSnapshotProvider for BtrfsProvider {
 
         match Self::fs_type(repo) {
             Ok(fs_type) if fs_type == "btrfs" => match self.get_subvolume_for_path(repo) {
-                Ok(subvolume) => ProviderCapabilities {
-                    kind: self.kind(),
-                    score: 80,
-                    supports_cow_overlay: true,
-                    notes: vec![format!("Using Btrfs subvolume: {}", subvolume)],
-                },
+                Ok(subvolume) => {
+                    let mut notes = vec![format!("Using Btrfs subvolume: {}", subvolume)];
+                    let mut score = 80;
+
+                    if unprivileged_userns_available() {
+                        score = 90;
+                        notes.push(
+                            "Unprivileged user namespace available - Worktree overlay-in-place mode can hold its own mount namespace without root".to_string(),
+                        );
+                    } else {
+                        notes.push(
+                            "Worktree overlay-in-place mode requires CAP_SYS_ADMIN (no unprivileged user namespace detected)".to_string(),
+                        );
+                    }
+
+                    ProviderCapabilities {
+                        kind: self.kind(),
+                        score,
+                        supports_cow_overlay: true,
+                        notes,
+                    }
+                }
                 Err(_) => ProviderCapabilities {
                     kind: self.kind(),
                     score: 0,
@@ -193,10 +509,7 @@ impl FsSnapshotProvider for BtrfsProvider {
                 })
             }
             WorkingCopyMode::Worktree | WorkingCopyMode::Auto => {
-                // Fall back to worktree mode for Btrfs (simpler implementation)
-                Err(ah_fs_snapshots_traits::Error::provider(
-                    "Btrfs worktree mode not implemented - use CowOverlay",
-                ))
+                self.prepare_worktree_overlay(repo)
             }
         }
     }
@@ -281,8 +594,62 @@ impl FsSnapshotProvider for BtrfsProvider {
                     cleanup_token: format!("btrfs:branch:{}", branch_path.display()),
                 })
             }
+            WorkingCopyMode::Worktree | WorkingCopyMode::Auto => {
+                let source_path = snap.meta.get("source_path").ok_or_else(|| {
+                    ah_fs_snapshots_traits::Error::provider(
+                        "Btrfs snapshot missing source_path metadata required for Worktree mode",
+                    )
+                })?;
+                let unique_id = self.generate_unique_id();
+                let subvolume_path = snap
+                    .meta
+                    .get("snapshot_path")
+                    .map(|p| Path::new(p).with_file_name(format!("ah_worktree_branch_{}", unique_id)))
+                    .unwrap_or_else(|| PathBuf::from(format!("ah_worktree_branch_{}", unique_id)));
+
+                self.execute_btrfs_command(&[
+                    "subvolume",
+                    "snapshot",
+                    snap.meta.get("snapshot_path").unwrap(),
+                    subvolume_path.to_str().unwrap(),
+                ])?;
+
+                let repo = Path::new(source_path);
+                enter_private_mount_namespace().map_err(|e| {
+                    let _ = self
+                        .execute_btrfs_command(&["subvolume", "delete", subvolume_path.to_str().unwrap()]);
+                    e
+                })?;
+
+                nix::mount::mount(
+                    Some(subvolume_path.as_path()),
+                    repo,
+                    None::<&str>,
+                    nix::mount::MsFlags::MS_BIND,
+                    None::<&str>,
+                )
+                .map_err(|e| {
+                    ah_fs_snapshots_traits::Error::provider(format!(
+                        "Failed to bind-mount writable branch {} over {}: {}",
+                        subvolume_path.display(),
+                        repo.display(),
+                        e
+                    ))
+                })?;
+
+                Ok(PreparedWorkspace {
+                    exec_path: repo.to_path_buf(),
+                    working_copy: WorkingCopyMode::Worktree,
+                    provider: self.kind(),
+                    cleanup_token: format!(
+                        "btrfs:worktree:{}:{}",
+                        repo.display(),
+                        subvolume_path.display()
+                    ),
+                })
+            }
             _ => Err(ah_fs_snapshots_traits::Error::provider(
-                "Btrfs branching only supports CowOverlay mode",
+                "Btrfs branching only supports CowOverlay and Worktree modes",
             )),
         }
     }
@@ -301,6 +668,22 @@ impl FsSnapshotProvider for BtrfsProvider {
                 let _ = self.execute_btrfs_command(&["subvolume", "delete", snapshot_path]);
             }
             Ok(())
+        } else if token.starts_with("btrfs:worktree:") {
+            // Format: btrfs:worktree:mount_point:subvolume_path
+            let rest = token.strip_prefix("btrfs:worktree:").unwrap_or(token);
+            let (mount_point, subvolume_path) = rest.split_once(':').ok_or_else(|| {
+                ah_fs_snapshots_traits::Error::provider(format!(
+                    "Invalid Btrfs worktree cleanup token: {}",
+                    token
+                ))
+            })?;
+
+            let _ = nix::mount::umount2(Path::new(mount_point), nix::mount::MntFlags::MNT_DETACH);
+
+            if Path::new(subvolume_path).exists() {
+                let _ = self.execute_btrfs_command(&["subvolume", "delete", subvolume_path]);
+            }
+            Ok(())
         } else if token.starts_with("btrfs:branch:") {
             // Format: btrfs:branch:branch_path
             let branch_path = token.strip_prefix("btrfs:branch:").unwrap_or(token);
@@ -53,6 +53,28 @@ pub fn provider_for(path: &Path) -> Result<Box<dyn FsSnapshotProvider>> {
     best_provider.ok_or_else(|| Error::provider("No suitable provider found"))
 }
 
+/// Return every provider compiled into this binary (via Cargo feature flags),
+/// regardless of how well it suits any particular path.
+///
+/// `provider_for` picks the single best provider for a path; this is for
+/// callers that instead want to compare providers against each other, e.g.
+/// `ah agent-fs benchmark`, which benchmarks every available provider and
+/// leaves deciding which one wins to the caller's own numbers.
+pub fn all_providers() -> Vec<Box<dyn FsSnapshotProvider>> {
+    let mut providers: Vec<Box<dyn FsSnapshotProvider>> = Vec::new();
+
+    #[cfg(feature = "zfs")]
+    providers.push(Box::new(ah_fs_snapshots_zfs::ZfsProvider::new()));
+
+    #[cfg(feature = "btrfs")]
+    providers.push(Box::new(ah_fs_snapshots_btrfs::BtrfsProvider::new()));
+
+    #[cfg(feature = "git")]
+    providers.push(Box::new(ah_fs_snapshots_git::GitProvider::new()));
+
+    providers
+}
+
 /// Validate a destination path for workspace creation.
 fn validate_destination_path(dest: &Path) -> Result<()> {
     // Check if the destination path can be created as a directory
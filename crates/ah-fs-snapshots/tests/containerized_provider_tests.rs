@@ -0,0 +1,146 @@
+//! Shared provider integration suite, parameterized by the filesystem under test.
+//!
+//! Unlike `zfs_integration_tests.rs`, which only ever exercises ZFS, this suite
+//! reads the `AH_FS_PROVIDER` environment variable (`zfs`, `btrfs`, or `overlay`)
+//! and provisions the matching loopback-backed filesystem from
+//! `filesystem_test_helpers` before running the same `FsSnapshotProvider`
+//! assertions against it. It is meant to be run once per provider inside the
+//! privileged containers that `tests/fs-snapshots-integration` brings up, so
+//! that contributors without, say, ZFS tooling on their own machine still get
+//! Btrfs/overlay coverage in CI.
+
+use ah_fs_snapshots_traits::{FsSnapshotProvider, WorkingCopyMode};
+use std::fs;
+use std::path::PathBuf;
+
+#[path = "filesystem_test_helpers.rs"]
+mod filesystem_test_helpers;
+
+use filesystem_test_helpers::{BtrfsTestEnvironment, OverlayTestEnvironment, ZfsTestEnvironment};
+
+/// Which provider to exercise, selected via the `AH_FS_PROVIDER` env var.
+enum TargetProvider {
+    Zfs,
+    Btrfs,
+    Overlay,
+}
+
+impl TargetProvider {
+    fn from_env() -> Option<Self> {
+        match std::env::var("AH_FS_PROVIDER").ok()?.as_str() {
+            "zfs" => Some(Self::Zfs),
+            "btrfs" => Some(Self::Btrfs),
+            "overlay" => Some(Self::Overlay),
+            _ => None,
+        }
+    }
+}
+
+/// Provision a repo directory on the target filesystem and return it, keeping
+/// whatever test-environment guard is needed alive for the duration of the test.
+fn provision_repo() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    match TargetProvider::from_env().expect(
+        "AH_FS_PROVIDER must be set to one of zfs/btrfs/overlay when running this test directly",
+    ) {
+        TargetProvider::Zfs => {
+            let mut env = ZfsTestEnvironment::new()?;
+            let mount_point = env.create_zfs_test_pool("containerized_zfs_pool", Some(500))?;
+            std::mem::forget(env); // keep the pool alive; the container is torn down as a whole
+            Ok(mount_point)
+        }
+        TargetProvider::Btrfs => {
+            let mut env = BtrfsTestEnvironment::new()?;
+            let mount_point = env.create_btrfs_test_volume("containerized_btrfs_volume", Some(500))?;
+            std::mem::forget(env);
+            Ok(mount_point)
+        }
+        TargetProvider::Overlay => {
+            let mut env = OverlayTestEnvironment::new()?;
+            let mount_point = env.create_overlay_test_mount("containerized_overlay_mount")?;
+            std::mem::forget(env);
+            Ok(mount_point)
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_provider_against_shared_suite() {
+    let target = match TargetProvider::from_env() {
+        Some(target) => target,
+        None => {
+            println!(
+                "Skipping containerized provider test: AH_FS_PROVIDER not set to zfs/btrfs/overlay"
+            );
+            return;
+        }
+    };
+
+    let repo = match provision_repo() {
+        Ok(path) => path,
+        Err(e) => {
+            println!("Skipping test: could not provision target filesystem: {}", e);
+            return;
+        }
+    };
+
+    fs::write(repo.join("README.md"), "Integration test repository").unwrap();
+    fs::write(repo.join("test_file.txt"), "Test content").unwrap();
+
+    let provider: Box<dyn FsSnapshotProvider> = match target {
+        #[cfg(feature = "zfs")]
+        TargetProvider::Zfs => Box::new(ah_fs_snapshots_zfs::ZfsProvider::new()),
+        #[cfg(not(feature = "zfs"))]
+        TargetProvider::Zfs => {
+            println!("ZFS feature not enabled, skipping");
+            return;
+        }
+        #[cfg(feature = "btrfs")]
+        TargetProvider::Btrfs => Box::new(ah_fs_snapshots_btrfs::BtrfsProvider::new()),
+        #[cfg(not(feature = "btrfs"))]
+        TargetProvider::Btrfs => {
+            println!("Btrfs feature not enabled, skipping");
+            return;
+        }
+        // No dedicated overlay provider crate exists yet; overlay coverage
+        // exercises the Git provider's copy-up path against an overlay-backed
+        // working tree instead of a provider-specific implementation.
+        #[cfg(feature = "git")]
+        TargetProvider::Overlay => Box::new(ah_fs_snapshots_git::GitProvider::new()),
+        #[cfg(not(feature = "git"))]
+        TargetProvider::Overlay => {
+            println!("Git feature not enabled, skipping overlay coverage");
+            return;
+        }
+    };
+
+    let ws_result = provider.prepare_writable_workspace(&repo, WorkingCopyMode::Worktree);
+    let workspace = match ws_result {
+        Ok(ws) => ws,
+        Err(e) => {
+            println!("Workspace creation failed: {}", e);
+            return;
+        }
+    };
+
+    assert!(workspace.exec_path.exists());
+    assert!(workspace.exec_path.join("README.md").exists());
+
+    let test_file = workspace.exec_path.join("integration_test.txt");
+    fs::write(&test_file, "integration test content").unwrap();
+    assert!(test_file.exists());
+
+    // Changes in the workspace must not leak back into the source repo.
+    assert!(!repo.join("integration_test.txt").exists());
+
+    let snapshot = provider
+        .snapshot_now(&workspace, Some("containerized-suite"))
+        .expect("snapshot_now should succeed against a freshly prepared workspace");
+
+    let branch = provider
+        .branch_from_snapshot(&snapshot, WorkingCopyMode::Worktree)
+        .expect("branch_from_snapshot should succeed from a just-taken snapshot");
+    assert!(branch.exec_path.join("integration_test.txt").exists());
+
+    let _ = provider.cleanup(&branch.cleanup_token);
+    let _ = provider.cleanup(&workspace.cleanup_token);
+}
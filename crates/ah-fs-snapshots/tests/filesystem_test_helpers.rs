@@ -1,7 +1,8 @@
-//! ZFS test helpers for creating test ZFS pools for testing.
+//! Filesystem test helpers for creating test ZFS pools, Btrfs volumes, and
+//! overlayfs mounts for testing.
 //!
-//! This module provides utilities for setting up ZFS test environments,
-//! similar to the ZFS portions of the legacy Ruby filesystem_test_helper.rb but implemented in Rust.
+//! This module provides utilities for setting up filesystem test environments,
+//! similar to the legacy Ruby filesystem_test_helper.rb but implemented in Rust.
 
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -183,6 +184,277 @@ impl Drop for ZfsTestEnvironment {
     }
 }
 
+/// A Btrfs test environment that manages loopback-backed Btrfs volumes and cleanup.
+pub struct BtrfsTestEnvironment {
+    /// Base directory for creating Btrfs device files and mount points.
+    pub test_dir: PathBuf,
+    /// List of created Btrfs volumes for cleanup.
+    pub btrfs_volumes: Vec<BtrfsVolumeInfo>,
+    /// Temporary directory handle (keeps it alive during tests).
+    _temp_dir: TempDir,
+}
+
+/// Information about a created Btrfs test volume.
+#[derive(Debug, Clone)]
+pub struct BtrfsVolumeInfo {
+    /// Path to the loopback device file backing the volume.
+    pub device_file: PathBuf,
+    /// Loopback device node (e.g. `/dev/loop7`) bound to `device_file`.
+    pub loop_device: PathBuf,
+    /// Mount point for the Btrfs filesystem.
+    pub mount_point: PathBuf,
+}
+
+impl BtrfsTestEnvironment {
+    /// Create a new Btrfs test environment with a temporary directory.
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let test_dir = temp_dir.path().to_path_buf();
+
+        Ok(Self {
+            test_dir,
+            btrfs_volumes: Vec::new(),
+            _temp_dir: temp_dir,
+        })
+    }
+
+    /// Create a Btrfs volume on a loopback device for testing.
+    ///
+    /// # Arguments
+    /// * `volume_name` - Name used for the device file and mount point.
+    /// * `size_mb` - Size of the underlying device file in megabytes (default: 500)
+    ///
+    /// # Returns
+    /// The mount point of the created Btrfs filesystem.
+    pub fn create_btrfs_test_volume(
+        &mut self,
+        volume_name: &str,
+        size_mb: Option<u32>,
+    ) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let size_mb = size_mb.unwrap_or(500);
+        let device_file = self.test_dir.join(format!("{}_device.img", volume_name));
+        let mount_point = self.test_dir.join(format!("{}_mount", volume_name));
+        fs::create_dir_all(&mount_point)?;
+
+        // Create device file
+        let dd_status = Command::new("dd")
+            .arg("if=/dev/zero")
+            .arg(format!("of={}", device_file.display()))
+            .arg("bs=1M")
+            .arg(format!("count={}", size_mb))
+            .status()?;
+
+        if !dd_status.success() {
+            return Err(format!(
+                "Failed to create Btrfs device file: {}",
+                device_file.display()
+            )
+            .into());
+        }
+
+        // Attach the device file to a loopback device
+        let losetup_output =
+            Command::new("losetup").arg("--find").arg("--show").arg(&device_file).output()?;
+
+        if !losetup_output.status.success() {
+            return Err("Failed to attach loopback device for Btrfs volume".into());
+        }
+        let loop_device = PathBuf::from(String::from_utf8(losetup_output.stdout)?.trim());
+
+        // Format the loopback device as Btrfs
+        let mkfs_status = Command::new("mkfs.btrfs")
+            .arg("-f")
+            .arg(loop_device.display().to_string())
+            .status()?;
+
+        if !mkfs_status.success() {
+            let _ = Command::new("losetup").arg("-d").arg(&loop_device).status();
+            return Err(format!("Failed to format Btrfs volume on {}", loop_device.display()).into());
+        }
+
+        // Mount the filesystem
+        let mount_status = Command::new("mount")
+            .arg(loop_device.display().to_string())
+            .arg(&mount_point)
+            .status()?;
+
+        if !mount_status.success() {
+            let _ = Command::new("losetup").arg("-d").arg(&loop_device).status();
+            return Err(format!("Failed to mount Btrfs volume at {}", mount_point.display()).into());
+        }
+
+        // Track for cleanup
+        let info = BtrfsVolumeInfo {
+            device_file,
+            loop_device,
+            mount_point: mount_point.clone(),
+        };
+        self.btrfs_volumes.push(info);
+
+        Ok(mount_point)
+    }
+
+    /// Get filesystem usage in bytes using df.
+    pub fn get_filesystem_used_space(
+        &self,
+        mount_point: &Path,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        get_filesystem_used_space_via_df(mount_point)
+    }
+
+    /// Unmount and detach all tracked Btrfs volumes.
+    pub fn cleanup_all_volumes(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        for volume_info in &self.btrfs_volumes {
+            let _ = Command::new("umount").arg(&volume_info.mount_point).status();
+            let _ = Command::new("losetup").arg("-d").arg(&volume_info.loop_device).status();
+        }
+
+        self.btrfs_volumes.clear();
+        Ok(())
+    }
+}
+
+impl Drop for BtrfsTestEnvironment {
+    fn drop(&mut self) {
+        let _ = self.cleanup_all_volumes();
+    }
+}
+
+/// An overlayfs test environment that manages lowerdir/upperdir/workdir mounts and cleanup.
+pub struct OverlayTestEnvironment {
+    /// Base directory holding the lower/upper/work/merged directories.
+    pub test_dir: PathBuf,
+    /// List of created overlay mounts for cleanup.
+    pub overlay_mounts: Vec<OverlayMountInfo>,
+    /// Temporary directory handle (keeps it alive during tests).
+    _temp_dir: TempDir,
+}
+
+/// Information about a created overlayfs test mount.
+#[derive(Debug, Clone)]
+pub struct OverlayMountInfo {
+    /// Read-only lower directory.
+    pub lower_dir: PathBuf,
+    /// Writable upper directory.
+    pub upper_dir: PathBuf,
+    /// Overlayfs working directory (required scratch space).
+    pub work_dir: PathBuf,
+    /// Merged mount point exposing the union view.
+    pub merged_dir: PathBuf,
+}
+
+impl OverlayTestEnvironment {
+    /// Create a new overlayfs test environment with a temporary directory.
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let test_dir = temp_dir.path().to_path_buf();
+
+        Ok(Self {
+            test_dir,
+            overlay_mounts: Vec::new(),
+            _temp_dir: temp_dir,
+        })
+    }
+
+    /// Create and mount an overlayfs union for testing.
+    ///
+    /// # Arguments
+    /// * `mount_name` - Name used for the lower/upper/work/merged directories.
+    ///
+    /// # Returns
+    /// The merged mount point exposing the union view.
+    pub fn create_overlay_test_mount(
+        &mut self,
+        mount_name: &str,
+    ) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let lower_dir = self.test_dir.join(format!("{}_lower", mount_name));
+        let upper_dir = self.test_dir.join(format!("{}_upper", mount_name));
+        let work_dir = self.test_dir.join(format!("{}_work", mount_name));
+        let merged_dir = self.test_dir.join(format!("{}_merged", mount_name));
+
+        for dir in [&lower_dir, &upper_dir, &work_dir, &merged_dir] {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mount_status = Command::new("mount")
+            .arg("-t")
+            .arg("overlay")
+            .arg("overlay")
+            .arg("-o")
+            .arg(format!(
+                "lowerdir={},upperdir={},workdir={}",
+                lower_dir.display(),
+                upper_dir.display(),
+                work_dir.display()
+            ))
+            .arg(&merged_dir)
+            .status()?;
+
+        if !mount_status.success() {
+            return Err(format!("Failed to mount overlayfs at {}", merged_dir.display()).into());
+        }
+
+        let info = OverlayMountInfo {
+            lower_dir,
+            upper_dir,
+            work_dir,
+            merged_dir: merged_dir.clone(),
+        };
+        self.overlay_mounts.push(info);
+
+        Ok(merged_dir)
+    }
+
+    /// Get filesystem usage in bytes using df.
+    pub fn get_filesystem_used_space(
+        &self,
+        mount_point: &Path,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        get_filesystem_used_space_via_df(mount_point)
+    }
+
+    /// Unmount all tracked overlayfs mounts.
+    pub fn cleanup_all_mounts(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        for mount_info in &self.overlay_mounts {
+            let _ = Command::new("umount").arg(&mount_info.merged_dir).status();
+        }
+
+        self.overlay_mounts.clear();
+        Ok(())
+    }
+}
+
+impl Drop for OverlayTestEnvironment {
+    fn drop(&mut self) {
+        let _ = self.cleanup_all_mounts();
+    }
+}
+
+/// Get filesystem usage in bytes for `mount_point` using `df`.
+///
+/// Shared by the Btrfs and overlay environments, which unlike ZFS don't have
+/// a richer accounting tool of their own to shell out to.
+fn get_filesystem_used_space_via_df(mount_point: &Path) -> Result<u64, Box<dyn std::error::Error>> {
+    let output = Command::new("df").arg("-B1").arg(mount_point).output()?;
+
+    if !output.status.success() {
+        return Ok(0);
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    if lines.len() < 2 {
+        return Ok(0);
+    }
+
+    let fields: Vec<&str> = lines[1].split_whitespace().collect();
+    if fields.len() < 3 {
+        return Ok(0);
+    }
+
+    fields[2].parse::<u64>().map_err(Into::into)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -235,6 +507,84 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_btrfs_test_environment_creation() {
+        let env = BtrfsTestEnvironment::new();
+        assert!(env.is_ok());
+        let env = env.unwrap();
+        assert!(env.test_dir.exists());
+    }
+
+    #[test]
+    fn test_btrfs_volume_creation() {
+        if !is_root() {
+            println!("Skipping Btrfs test: requires root privileges");
+            return;
+        }
+
+        let mut env = BtrfsTestEnvironment::new().unwrap();
+
+        let result = env.create_btrfs_test_volume("test_btrfs_volume", Some(200));
+        match result {
+            Ok(mount_point) => {
+                println!("Successfully created Btrfs volume at: {:?}", mount_point);
+                assert!(mount_point.exists());
+
+                let test_file = mount_point.join("test.txt");
+                fs::write(&test_file, "Btrfs test content").unwrap();
+                assert!(test_file.exists());
+
+                let content = fs::read_to_string(&test_file).unwrap();
+                assert_eq!(content, "Btrfs test content");
+            }
+            Err(e) => {
+                println!(
+                    "Btrfs volume creation failed (expected in some environments): {}",
+                    e
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlay_test_environment_creation() {
+        let env = OverlayTestEnvironment::new();
+        assert!(env.is_ok());
+        let env = env.unwrap();
+        assert!(env.test_dir.exists());
+    }
+
+    #[test]
+    fn test_overlay_mount_creation() {
+        if !is_root() {
+            println!("Skipping overlay test: requires root privileges");
+            return;
+        }
+
+        let mut env = OverlayTestEnvironment::new().unwrap();
+
+        let result = env.create_overlay_test_mount("test_overlay_mount");
+        match result {
+            Ok(merged_dir) => {
+                println!("Successfully mounted overlay at: {:?}", merged_dir);
+                assert!(merged_dir.exists());
+
+                let test_file = merged_dir.join("test.txt");
+                fs::write(&test_file, "overlay test content").unwrap();
+                assert!(test_file.exists());
+
+                let content = fs::read_to_string(&test_file).unwrap();
+                assert_eq!(content, "overlay test content");
+            }
+            Err(e) => {
+                println!(
+                    "Overlay mount creation failed (expected in some environments): {}",
+                    e
+                );
+            }
+        }
+    }
+
     fn is_root() -> bool {
         unsafe { libc::geteuid() == 0 }
     }
@@ -3,6 +3,7 @@
 //! This module contains comprehensive integration tests for all filesystem
 //! snapshot providers, using the test infrastructure ported from the legacy Ruby tests.
 
+pub mod containerized_provider_tests;
 pub mod filesystem_test_helpers;
 pub mod space_utils;
 pub mod zfs_integration_tests;
@@ -1,149 +1,265 @@
 //! Database migration management.
+//!
+//! Migrations are plain SQL pairs keyed by an ascending `version` number.
+//! Applying a migration and recording it in `schema_migrations` happens in a
+//! single transaction, so a crash mid-upgrade always leaves the database at
+//! a clean, previously-applied version.
 
+use crate::schema::SCHEMA_VERSION;
 use rusqlite::{params, Connection};
 
-/// Database migration manager.
-pub struct MigrationManager;
+/// A single schema change: SQL to move forward one version and SQL to undo it.
+pub struct Migration {
+    pub version: u32,
+    pub up: &'static str,
+    pub down: &'static str,
+}
 
-impl MigrationManager {
-    /// Apply all pending migrations to the database.
-    pub fn migrate(conn: &Connection) -> crate::Result<()> {
-        // Create schema migrations table first
-        conn.execute_batch(
-            r#"
-            CREATE TABLE IF NOT EXISTS schema_migrations (
-                version INTEGER PRIMARY KEY,
-                applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
-            );
-            "#,
-        )?;
+/// All migrations in ascending `version` order. The last entry's `version`
+/// must equal [`SCHEMA_VERSION`].
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    up: r#"
+        -- Repositories known to the system (local path and/or remote URL)
+        CREATE TABLE IF NOT EXISTS repos (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            vcs TEXT NOT NULL,
+            root_path TEXT,
+            remote_url TEXT,
+            default_branch TEXT,
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            UNIQUE(root_path),
+            UNIQUE(remote_url)
+        );
 
-        // Get current version
-        let current_version = Self::current_version(conn)?.unwrap_or(0);
+        -- Workspaces are named logical groupings on some servers. Optional locally.
+        CREATE TABLE IF NOT EXISTS workspaces (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            external_id TEXT,
+            created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            UNIQUE(name)
+        );
 
-        // Apply migrations sequentially
-        if current_version < 1 {
-            Self::apply_migration_1(conn)?;
-        }
+        -- Agents catalog (type + version descriptor)
+        CREATE TABLE IF NOT EXISTS agents (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            version TEXT NOT NULL,
+            metadata TEXT,
+            UNIQUE(name, version)
+        );
 
-        Ok(())
+        -- Runtime definitions (devcontainer, local, disabled, etc.)
+        CREATE TABLE IF NOT EXISTS runtimes (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            type TEXT NOT NULL,
+            devcontainer_path TEXT,
+            metadata TEXT
+        );
+
+        -- Sessions are concrete agent runs bound to a repo (and optionally a workspace)
+        CREATE TABLE IF NOT EXISTS sessions (
+            id TEXT PRIMARY KEY,
+            repo_id INTEGER REFERENCES repos(id) ON DELETE RESTRICT,
+            workspace_id INTEGER REFERENCES workspaces(id) ON DELETE SET NULL,
+            agent_id INTEGER REFERENCES agents(id) ON DELETE RESTRICT,
+            runtime_id INTEGER REFERENCES runtimes(id) ON DELETE RESTRICT,
+            multiplexer_kind TEXT,
+            mux_session TEXT,
+            mux_window INTEGER,
+            pane_left TEXT,
+            pane_right TEXT,
+            pid_agent INTEGER,
+            status TEXT NOT NULL,
+            log_path TEXT,
+            workspace_path TEXT,
+            started_at TEXT NOT NULL,
+            ended_at TEXT
+        );
+
+        -- Tasks capture user intent and parameters used to launch a session
+        CREATE TABLE IF NOT EXISTS tasks (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            prompt TEXT NOT NULL,
+            branch TEXT,
+            delivery TEXT,
+            instances INTEGER DEFAULT 1,
+            labels TEXT,
+            browser_automation INTEGER NOT NULL DEFAULT 1,
+            browser_profile TEXT,
+            chatgpt_username TEXT,
+            codex_workspace TEXT
+        );
+
+        -- Event log per session for diagnostics and incremental state
+        CREATE TABLE IF NOT EXISTS events (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            ts TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            type TEXT NOT NULL,
+            data TEXT
+        );
+
+        -- Filesystem snapshots associated with a session (see docs/fs-snapshots)
+        CREATE TABLE IF NOT EXISTS fs_snapshots (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+            ts TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            provider TEXT NOT NULL,
+            ref TEXT,
+            path TEXT,
+            parent_id INTEGER REFERENCES fs_snapshots(id) ON DELETE SET NULL,
+            metadata TEXT
+        );
+
+        -- Key/value subsystem for small, fast lookups (scoped configuration, caches)
+        CREATE TABLE IF NOT EXISTS kv (
+            scope TEXT NOT NULL,
+            k TEXT NOT NULL,
+            v TEXT,
+            PRIMARY KEY (scope, k)
+        );
+
+        -- Indexes for performance
+        CREATE INDEX IF NOT EXISTS idx_events_session_ts ON events(session_id, ts);
+        CREATE INDEX IF NOT EXISTS idx_fs_snapshots_session_ts ON fs_snapshots(session_id, ts);
+    "#,
+    down: r#"
+        DROP INDEX IF EXISTS idx_fs_snapshots_session_ts;
+        DROP INDEX IF EXISTS idx_events_session_ts;
+        DROP TABLE IF EXISTS kv;
+        DROP TABLE IF EXISTS fs_snapshots;
+        DROP TABLE IF EXISTS events;
+        DROP TABLE IF EXISTS tasks;
+        DROP TABLE IF EXISTS sessions;
+        DROP TABLE IF EXISTS runtimes;
+        DROP TABLE IF EXISTS agents;
+        DROP TABLE IF EXISTS workspaces;
+        DROP TABLE IF EXISTS repos;
+    "#,
+}, Migration {
+    version: 2,
+    up: r#"
+        -- Structured error history, so a session's failures are queryable
+        -- after the fact instead of only ever reaching stderr.
+        CREATE TABLE IF NOT EXISTS errors (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            session_id TEXT REFERENCES sessions(id) ON DELETE CASCADE,
+            ts TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+            kind TEXT NOT NULL,
+            message TEXT NOT NULL,
+            context TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_errors_session_ts ON errors(session_id, ts);
+    "#,
+    down: r#"
+        DROP INDEX IF EXISTS idx_errors_session_ts;
+        DROP TABLE IF EXISTS errors;
+    "#,
+}, Migration {
+    version: 3,
+    up: r#"
+        -- Durable task-manager task state (see ah_core::task::TaskManager),
+        -- keyed by the same numeric id the in-process TaskManager hands out,
+        -- so a TaskStore::load_all on startup can rehydrate both the task
+        -- list and the next-id counter.
+        CREATE TABLE IF NOT EXISTS task_states (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            description TEXT NOT NULL,
+            status TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            metadata TEXT
+        );
+    "#,
+    down: r#"
+        DROP TABLE IF EXISTS task_states;
+    "#,
+}];
+
+/// Ensure `schema_migrations` exists and return the highest applied version,
+/// or `0` for a brand-new database.
+fn ensure_migrations_table(conn: &Connection) -> crate::Result<u32> {
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            applied_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now'))
+        );
+        "#,
+    )?;
+
+    let version: Option<u32> = conn
+        .prepare("SELECT MAX(version) FROM schema_migrations")?
+        .query_row(params![], |row| row.get(0))?;
+
+    Ok(version.unwrap_or(0))
+}
+
+/// Apply every migration with a version greater than the one already
+/// recorded in `schema_migrations`, in ascending order. Each migration's
+/// `up` SQL and its `schema_migrations` row are committed in one
+/// transaction. Returns the resulting schema version.
+///
+/// Refuses to touch a database whose recorded version is newer than this
+/// binary's [`SCHEMA_VERSION`] (the database was created by a newer build).
+pub fn migrate_up(conn: &Connection) -> crate::Result<u32> {
+    let current_version = ensure_migrations_table(conn)?;
+
+    if current_version > SCHEMA_VERSION {
+        return Err(crate::Error::generic(format!(
+            "database schema version {} is newer than supported version {}",
+            current_version, SCHEMA_VERSION
+        )));
     }
 
-    /// Apply migration version 1 - complete State-Persistence.md schema
-    fn apply_migration_1(conn: &Connection) -> crate::Result<()> {
-        conn.execute_batch(
-            r#"
-            -- Repositories known to the system (local path and/or remote URL)
-            CREATE TABLE IF NOT EXISTS repos (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                vcs TEXT NOT NULL,
-                root_path TEXT,
-                remote_url TEXT,
-                default_branch TEXT,
-                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
-                UNIQUE(root_path),
-                UNIQUE(remote_url)
-            );
-
-            -- Workspaces are named logical groupings on some servers. Optional locally.
-            CREATE TABLE IF NOT EXISTS workspaces (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                external_id TEXT,
-                created_at TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
-                UNIQUE(name)
-            );
-
-            -- Agents catalog (type + version descriptor)
-            CREATE TABLE IF NOT EXISTS agents (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                version TEXT NOT NULL,
-                metadata TEXT,
-                UNIQUE(name, version)
-            );
-
-            -- Runtime definitions (devcontainer, local, disabled, etc.)
-            CREATE TABLE IF NOT EXISTS runtimes (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                type TEXT NOT NULL,
-                devcontainer_path TEXT,
-                metadata TEXT
-            );
-
-            -- Sessions are concrete agent runs bound to a repo (and optionally a workspace)
-            CREATE TABLE IF NOT EXISTS sessions (
-                id TEXT PRIMARY KEY,
-                repo_id INTEGER REFERENCES repos(id) ON DELETE RESTRICT,
-                workspace_id INTEGER REFERENCES workspaces(id) ON DELETE SET NULL,
-                agent_id INTEGER REFERENCES agents(id) ON DELETE RESTRICT,
-                runtime_id INTEGER REFERENCES runtimes(id) ON DELETE RESTRICT,
-                multiplexer_kind TEXT,
-                mux_session TEXT,
-                mux_window INTEGER,
-                pane_left TEXT,
-                pane_right TEXT,
-                pid_agent INTEGER,
-                status TEXT NOT NULL,
-                log_path TEXT,
-                workspace_path TEXT,
-                started_at TEXT NOT NULL,
-                ended_at TEXT
-            );
-
-            -- Tasks capture user intent and parameters used to launch a session
-            CREATE TABLE IF NOT EXISTS tasks (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
-                prompt TEXT NOT NULL,
-                branch TEXT,
-                delivery TEXT,
-                instances INTEGER DEFAULT 1,
-                labels TEXT,
-                browser_automation INTEGER NOT NULL DEFAULT 1,
-                browser_profile TEXT,
-                chatgpt_username TEXT,
-                codex_workspace TEXT
-            );
-
-            -- Event log per session for diagnostics and incremental state
-            CREATE TABLE IF NOT EXISTS events (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
-                ts TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
-                type TEXT NOT NULL,
-                data TEXT
-            );
-
-            -- Filesystem snapshots associated with a session (see docs/fs-snapshots)
-            CREATE TABLE IF NOT EXISTS fs_snapshots (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
-                ts TEXT NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
-                provider TEXT NOT NULL,
-                ref TEXT,
-                path TEXT,
-                parent_id INTEGER REFERENCES fs_snapshots(id) ON DELETE SET NULL,
-                metadata TEXT
-            );
-
-            -- Key/value subsystem for small, fast lookups (scoped configuration, caches)
-            CREATE TABLE IF NOT EXISTS kv (
-                scope TEXT NOT NULL,
-                k TEXT NOT NULL,
-                v TEXT,
-                PRIMARY KEY (scope, k)
-            );
-
-            -- Indexes for performance
-            CREATE INDEX IF NOT EXISTS idx_events_session_ts ON events(session_id, ts);
-            CREATE INDEX IF NOT EXISTS idx_fs_snapshots_session_ts ON fs_snapshots(session_id, ts);
-
-            -- Mark migration as applied
-            INSERT OR REPLACE INTO schema_migrations (version) VALUES (1);
-            "#,
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current_version) {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(migration.up)?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version) VALUES (?1)",
+            params![migration.version],
+        )?;
+        tx.commit()?;
+    }
+
+    ensure_migrations_table(conn)
+}
+
+/// Roll back migrations in descending order until the database is at
+/// `target` version, running each migration's `down` SQL and removing its
+/// `schema_migrations` row in one transaction.
+pub fn migrate_down(conn: &Connection, target: u32) -> crate::Result<()> {
+    let current_version = ensure_migrations_table(conn)?;
+
+    for migration in MIGRATIONS.iter().rev().filter(|m| m.version > target && m.version <= current_version) {
+        let tx = conn.unchecked_transaction()?;
+        tx.execute_batch(migration.down)?;
+        tx.execute(
+            "DELETE FROM schema_migrations WHERE version = ?1",
+            params![migration.version],
         )?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
 
+/// Database migration manager.
+///
+/// Thin, stateless wrapper kept for callers that just want to bring a fresh
+/// or older database up to [`SCHEMA_VERSION`] without dealing with versions
+/// directly.
+pub struct MigrationManager;
+
+impl MigrationManager {
+    /// Apply all pending migrations to the database.
+    pub fn migrate(conn: &Connection) -> crate::Result<()> {
+        migrate_up(conn)?;
         Ok(())
     }
 
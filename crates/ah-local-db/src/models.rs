@@ -190,6 +190,17 @@ pub struct FsSnapshotRecord {
     pub metadata: Option<String>,
 }
 
+/// Database model for structured error records.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorRecord {
+    pub id: i64,
+    pub session_id: Option<String>,
+    pub ts: String,
+    pub kind: String,
+    pub message: String,
+    pub context: Option<String>,
+}
+
 /// Database operations for repositories.
 pub struct RepoStore<'a> {
     conn: &'a rusqlite::Connection,
@@ -557,6 +568,226 @@ impl<'a> FsSnapshotStore<'a> {
     }
 }
 
+/// Database operations for structured errors.
+pub struct ErrorStore<'a> {
+    conn: &'a rusqlite::Connection,
+}
+
+impl<'a> ErrorStore<'a> {
+    pub fn new(conn: &'a rusqlite::Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert(
+        &self,
+        session_id: Option<&str>,
+        kind: &str,
+        message: &str,
+        context: Option<&str>,
+    ) -> crate::Result<i64> {
+        self.conn.execute(
+            r#"
+            INSERT INTO errors (session_id, kind, message, context)
+            VALUES (?, ?, ?, ?)
+            "#,
+            params![session_id, kind, message, context],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    pub fn list_by_session(&self, session_id: &str) -> crate::Result<Vec<ErrorRecord>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, session_id, ts, kind, message, context
+            FROM errors
+            WHERE session_id = ?
+            ORDER BY ts ASC
+            "#,
+        )?;
+
+        let records = stmt.query_map(params![session_id], |row| {
+            Ok(ErrorRecord {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                ts: row.get(2)?,
+                kind: row.get(3)?,
+                message: row.get(4)?,
+                context: row.get(5)?,
+            })
+        })?;
+
+        let mut errors = Vec::new();
+        for record in records {
+            errors.push(record?);
+        }
+        Ok(errors)
+    }
+}
+
+/// Database model for a `TaskManager` task's durable state.
+///
+/// Mirrors `ah_core::task::Task` field-for-field; kept as a plain row type
+/// here (rather than depending on `ah-core`) the same way `TaskRecord`
+/// mirrors the launch-parameter `Task` above.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStateRecord {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub status: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub metadata: Option<String>,
+}
+
+/// Database operations for `TaskManager` task state.
+pub struct TaskStateStore<'a> {
+    conn: &'a rusqlite::Connection,
+}
+
+impl<'a> TaskStateStore<'a> {
+    pub fn new(conn: &'a rusqlite::Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert(&self, record: &TaskStateRecord) -> crate::Result<()> {
+        self.conn.execute(
+            r#"
+            INSERT INTO task_states (id, name, description, status, created_at, updated_at, metadata)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+            params![
+                record.id,
+                record.name,
+                record.description,
+                record.status,
+                record.created_at,
+                record.updated_at,
+                record.metadata
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: i64) -> crate::Result<Option<TaskStateRecord>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, name, description, status, created_at, updated_at, metadata
+            FROM task_states WHERE id = ?
+            "#,
+        )?;
+
+        let mut rows = stmt.query_map(params![id], |row| {
+            Ok(TaskStateRecord {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                status: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+                metadata: row.get(6)?,
+            })
+        })?;
+
+        match rows.next() {
+            Some(Ok(record)) => Ok(Some(record)),
+            Some(Err(e)) => Err(e.into()),
+            None => Ok(None),
+        }
+    }
+
+    pub fn update_status(&self, id: i64, status: &str, updated_at: &str) -> crate::Result<()> {
+        self.conn.execute(
+            r#"
+            UPDATE task_states
+            SET status = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+            params![status, updated_at, id],
+        )?;
+        Ok(())
+    }
+
+    /// List every task state, oldest (lowest id) first, for `TaskStore::list`
+    /// and `TaskStore::load_all`.
+    pub fn list(&self) -> crate::Result<Vec<TaskStateRecord>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, name, description, status, created_at, updated_at, metadata
+            FROM task_states
+            ORDER BY id ASC
+            "#,
+        )?;
+
+        let records = stmt.query_map(params![], |row| {
+            Ok(TaskStateRecord {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                status: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+                metadata: row.get(6)?,
+            })
+        })?;
+
+        let mut states = Vec::new();
+        for record in records {
+            states.push(record?);
+        }
+        Ok(states)
+    }
+}
+
+/// Database operations for the session event timeline.
+pub struct EventStore<'a> {
+    conn: &'a rusqlite::Connection,
+}
+
+impl<'a> EventStore<'a> {
+    pub fn new(conn: &'a rusqlite::Connection) -> Self {
+        Self { conn }
+    }
+
+    pub fn insert(&self, session_id: &str, ts: &str, type_: &str, data: Option<&str>) -> crate::Result<i64> {
+        self.conn.execute(
+            r#"
+            INSERT INTO events (session_id, ts, type, data)
+            VALUES (?, ?, ?, ?)
+            "#,
+            params![session_id, ts, type_, data],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    pub fn list_by_session(&self, session_id: &str) -> crate::Result<Vec<EventRecord>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT id, session_id, ts, type, data
+            FROM events
+            WHERE session_id = ?
+            ORDER BY ts ASC
+            "#,
+        )?;
+
+        let records = stmt.query_map(params![session_id], |row| {
+            Ok(EventRecord {
+                id: row.get(0)?,
+                session_id: row.get(1)?,
+                ts: row.get(2)?,
+                type_: row.get(3)?,
+                data: row.get(4)?,
+            })
+        })?;
+
+        let mut events = Vec::new();
+        for record in records {
+            events.push(record?);
+        }
+        Ok(events)
+    }
+}
+
 /// Database operations for key-value store.
 pub struct KvStore<'a> {
     conn: &'a rusqlite::Connection,
@@ -1,7 +1,7 @@
 //! Database schema definitions and constants.
 
 // Current schema version
-pub const SCHEMA_VERSION: u32 = 1;
+pub const SCHEMA_VERSION: u32 = 3;
 
 // Table names
 pub const TABLE_SCHEMA_MIGRATIONS: &str = "schema_migrations";
@@ -14,6 +14,8 @@ pub const TABLE_TASKS: &str = "tasks";
 pub const TABLE_EVENTS: &str = "events";
 pub const TABLE_FS_SNAPSHOTS: &str = "fs_snapshots";
 pub const TABLE_KV: &str = "kv";
+pub const TABLE_ERRORS: &str = "errors";
+pub const TABLE_TASK_STATES: &str = "task_states";
 
 // Column names for repos table
 pub mod repos {
@@ -111,3 +113,24 @@ pub mod kv {
     pub const K: &str = "k";
     pub const V: &str = "v";
 }
+
+// Column names for errors table
+pub mod errors {
+    pub const ID: &str = "id";
+    pub const SESSION_ID: &str = "session_id";
+    pub const TS: &str = "ts";
+    pub const KIND: &str = "kind";
+    pub const MESSAGE: &str = "message";
+    pub const CONTEXT: &str = "context";
+}
+
+// Column names for task_states table
+pub mod task_states {
+    pub const ID: &str = "id";
+    pub const NAME: &str = "name";
+    pub const DESCRIPTION: &str = "description";
+    pub const STATUS: &str = "status";
+    pub const CREATED_AT: &str = "created_at";
+    pub const UPDATED_AT: &str = "updated_at";
+    pub const METADATA: &str = "metadata";
+}
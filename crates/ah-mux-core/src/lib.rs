@@ -81,6 +81,43 @@ pub trait Multiplexer {
 
     /// Discover panes within a window (best effort).
     fn list_panes(&self, window: &WindowId) -> Result<Vec<PaneId>, MuxError>;
+
+    /// Break a pane out of its current window into a new top-level window.
+    /// Returns the new WindowId. Optional to implement; may return NotAvailable.
+    fn break_pane(&self, pane: &PaneId) -> Result<WindowId, MuxError> {
+        let _ = pane;
+        Err(MuxError::NotAvailable(self.id()))
+    }
+
+    /// Join `src_pane` into `dst_window`, splitting it in the given direction
+    /// with an optional size percentage. Returns the PaneId of the joined pane
+    /// inside its new window. Optional to implement; may return NotAvailable.
+    fn join_pane(
+        &self,
+        src_pane: &PaneId,
+        dst_window: &WindowId,
+        dir: SplitDirection,
+        percent: Option<u8>,
+    ) -> Result<PaneId, MuxError> {
+        let _ = (src_pane, dst_window, dir, percent);
+        Err(MuxError::NotAvailable(self.id()))
+    }
+
+    /// Move `src` window to `dst`, renumbering/replacing as the implementation
+    /// sees fit. Returns the resulting WindowId. Optional to implement; may
+    /// return NotAvailable.
+    fn move_window(&self, src: &WindowId, dst: &WindowId) -> Result<WindowId, MuxError> {
+        let _ = (src, dst);
+        Err(MuxError::NotAvailable(self.id()))
+    }
+
+    /// Link `src` window into `dst`, making it appear in both places without
+    /// moving it. Returns the resulting WindowId. Optional to implement; may
+    /// return NotAvailable.
+    fn link_window(&self, src: &WindowId, dst: &WindowId) -> Result<WindowId, MuxError> {
+        let _ = (src, dst);
+        Err(MuxError::NotAvailable(self.id()))
+    }
 }
 
 #[cfg(test)]
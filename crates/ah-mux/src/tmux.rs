@@ -274,6 +274,57 @@ impl Multiplexer for TmuxMultiplexer {
         Ok(windows)
     }
 
+    fn break_pane(&self, pane: &PaneId) -> Result<WindowId, MuxError> {
+        let output = self.run_tmux_command(&["break-pane", "-P", "-s", pane])?;
+
+        // break-pane -P returns session:window.pane, but we need session:window as WindowId
+        let pane_id = output.trim();
+        let window_id = if let Some(dot_pos) = pane_id.rfind('.') {
+            pane_id[..dot_pos].to_string()
+        } else {
+            pane_id.to_string()
+        };
+
+        Ok(window_id)
+    }
+
+    fn join_pane(
+        &self,
+        src_pane: &PaneId,
+        dst_window: &WindowId,
+        dir: SplitDirection,
+        percent: Option<u8>,
+    ) -> Result<PaneId, MuxError> {
+        let mut args = vec!["join-pane".to_string(), "-P".to_string()];
+
+        match dir {
+            SplitDirection::Horizontal => args.push("-h".to_string()),
+            SplitDirection::Vertical => args.push("-v".to_string()),
+        }
+
+        if let Some(p) = percent {
+            args.extend_from_slice(&["-p".to_string(), p.to_string()]);
+        }
+
+        args.extend_from_slice(&["-s".to_string(), src_pane.clone()]);
+        args.extend_from_slice(&["-t".to_string(), dst_window.clone()]);
+
+        let args_str: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        let output = self.run_tmux_command(&args_str)?;
+
+        Ok(output.trim().to_string())
+    }
+
+    fn move_window(&self, src: &WindowId, dst: &WindowId) -> Result<WindowId, MuxError> {
+        self.run_tmux_command(&["move-window", "-s", src, "-t", dst])?;
+        Ok(dst.clone())
+    }
+
+    fn link_window(&self, src: &WindowId, dst: &WindowId) -> Result<WindowId, MuxError> {
+        self.run_tmux_command(&["link-window", "-s", src, "-t", dst])?;
+        Ok(dst.clone())
+    }
+
     fn list_panes(&self, window: &WindowId) -> Result<Vec<PaneId>, MuxError> {
         // The window parameter might be a pane ID (session:window.pane), extract just the window part
         let window_target = if window.contains('.') {
@@ -963,6 +1014,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_break_and_join_pane() {
+        let tmux = TmuxMultiplexer::with_session_name("test-break-join".to_string());
+        if tmux.is_available() {
+            // Clean up
+            let _ = tmux.run_tmux_command(&["kill-session", "-t", "test-break-join"]);
+
+            let window_id = tmux
+                .open_window(&WindowOptions {
+                    title: Some("break-join-test"),
+                    cwd: Some(Path::new("/tmp")),
+                    profile: None,
+                    focus: false,
+                })
+                .unwrap();
+
+            let initial_pane = format!("{}.0", window_id);
+
+            // Split to get a second pane
+            let split_pane = tmux
+                .split_pane(
+                    &window_id,
+                    Some(&initial_pane),
+                    SplitDirection::Horizontal,
+                    None,
+                    &CommandOptions::default(),
+                    None,
+                )
+                .unwrap();
+
+            // Break the split pane out into its own window
+            let broken_window = tmux.break_pane(&split_pane).unwrap();
+            assert!(broken_window.starts_with("test-break-join:"));
+            assert_ne!(broken_window, window_id);
+
+            // Only the original pane should remain in the original window
+            let panes = tmux.list_panes(&window_id).unwrap();
+            assert_eq!(panes.len(), 1);
+
+            // Join the broken-out pane back into the original window
+            let broken_pane = format!("{}.0", broken_window);
+            let joined_pane = tmux
+                .join_pane(&broken_pane, &window_id, SplitDirection::Vertical, Some(50))
+                .unwrap();
+            assert!(joined_pane.starts_with("test-break-join:"));
+
+            let panes = tmux.list_panes(&window_id).unwrap();
+            assert_eq!(panes.len(), 2);
+
+            // Clean up
+            let _ = tmux.run_tmux_command(&["kill-session", "-t", "test-break-join"]);
+        }
+    }
+
+    #[test]
+    fn test_move_and_link_window() {
+        let tmux1 = TmuxMultiplexer::with_session_name("test-move-src".to_string());
+        let tmux2 = TmuxMultiplexer::with_session_name("test-move-dst".to_string());
+
+        if tmux1.is_available() && tmux2.is_available() {
+            // Clean up
+            let _ = tmux1.run_tmux_command(&["kill-session", "-t", "test-move-src"]);
+            let _ = tmux2.run_tmux_command(&["kill-session", "-t", "test-move-dst"]);
+
+            let src_window = tmux1
+                .open_window(&WindowOptions {
+                    title: Some("move-src-window"),
+                    cwd: Some(Path::new("/tmp")),
+                    profile: None,
+                    focus: false,
+                })
+                .unwrap();
+
+            tmux2
+                .open_window(&WindowOptions {
+                    title: Some("move-dst-placeholder"),
+                    cwd: Some(Path::new("/tmp")),
+                    profile: None,
+                    focus: false,
+                })
+                .unwrap();
+
+            let dst_target = "test-move-dst:5".to_string();
+            let moved = tmux1.move_window(&src_window, &dst_target).unwrap();
+            assert_eq!(moved, dst_target);
+
+            // The window should now live in the destination session
+            let dst_windows = tmux2.list_windows(Some("move-src-window")).unwrap();
+            assert_eq!(dst_windows.len(), 1);
+
+            // Link a window from the destination session back for display elsewhere
+            let link_target = "test-move-src:5".to_string();
+            let linked = tmux2.link_window(&dst_target, &link_target).unwrap();
+            assert_eq!(linked, link_target);
+
+            // Clean up
+            let _ = tmux1.run_tmux_command(&["kill-session", "-t", "test-move-src"]);
+            let _ = tmux2.run_tmux_command(&["kill-session", "-t", "test-move-dst"]);
+        }
+    }
+
     #[test]
     fn test_tmux_not_available() {
         // Test behavior when tmux is not available
@@ -260,6 +260,15 @@ impl VcsRepo {
         Ok(())
     }
 
+    /// Replace the message of the current HEAD commit, keeping its tree and
+    /// author unchanged. Used to attach a `Pull-Request:` trailer once a PR
+    /// has been opened for a task whose start commit already exists.
+    pub fn amend_commit_message(&self, message: &str) -> VcsResult<()> {
+        let cmd = self.get_amend_commit_message_command(message);
+        self.run_command(&cmd)?;
+        Ok(())
+    }
+
     // Private helper methods
 
     fn find_repo_root<P: AsRef<Path>>(start_path: P) -> VcsResult<PathBuf> {
@@ -775,4 +784,38 @@ impl VcsRepo {
             ],
         }
     }
+
+    fn get_amend_commit_message_command(&self, message: &str) -> Vec<String> {
+        match self.vcs_type {
+            VcsType::Git => vec![
+                "git".to_string(),
+                "commit".to_string(),
+                "--amend".to_string(),
+                "-m".to_string(),
+                message.to_string(),
+            ],
+            VcsType::Hg => vec![
+                "hg".to_string(),
+                "commit".to_string(),
+                "--amend".to_string(),
+                "-m".to_string(),
+                message.to_string(),
+            ],
+            VcsType::Bzr => vec![
+                "bzr".to_string(),
+                "commit".to_string(),
+                "--fixes".to_string(),
+                "--unchanged".to_string(),
+                "-m".to_string(),
+                message.to_string(),
+            ],
+            VcsType::Fossil => vec![
+                "fossil".to_string(),
+                "amend".to_string(),
+                "current".to_string(),
+                "-m".to_string(),
+                message.to_string(),
+            ],
+        }
+    }
 }
@@ -5,6 +5,7 @@
 //! the mock server, production server, and REST client implementations.
 
 pub mod error;
+pub mod openapi;
 pub mod types;
 pub mod validation;
 
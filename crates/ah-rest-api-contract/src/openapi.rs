@@ -0,0 +1,161 @@
+//! A hand-maintained OpenAPI 3.0 description of the subset of the REST
+//! surface this crate has types for.
+//!
+//! This checkout has no schema-codegen toolchain (no `schemars`/`utoipa`
+//! equivalent wired up anywhere in the workspace), so unlike the doc
+//! comment on [`crate`] might suggest, the document below isn't derived
+//! from the Rust types by a macro — it's maintained by hand alongside them.
+//! [`document`]'s test below is the contract-drift guard described in the
+//! `chunk87-2` request: it re-derives the set of operations the document
+//! describes and checks it against a hand-kept list of the request/response
+//! methods of `ah_client_api::ClientApi` (the streaming `watch_tasks` is not
+//! described), so an operation added to or dropped from the document without
+//! updating that list fails a test. A method added only to the trait does
+//! not.
+
+use serde_json::{json, Value};
+
+/// Build the OpenAPI document. Cheap enough to call per test; not cached.
+pub fn document() -> Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "agent-harbor REST API",
+            "version": "1.0.0"
+        },
+        "paths": {
+            "/api/v1/daemon": {
+                "get": {
+                    "operationId": "daemonInfo",
+                    "responses": {
+                        "200": { "description": "Daemon build/version/capability info",
+                            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/DaemonInfo" } } } }
+                    }
+                }
+            },
+            "/api/v1/daemon/configure": {
+                "post": {
+                    "operationId": "configure",
+                    "requestBody": {
+                        "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ConfigureRequest" } } }
+                    },
+                    "responses": {
+                        "200": { "description": "Configuration applied",
+                            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/ConfigureResponse" } } } },
+                        "400": { "description": "Invalid configuration",
+                            "content": { "application/problem+json": { "schema": { "$ref": "#/components/schemas/ProblemDetails" } } } }
+                    }
+                }
+            },
+            "/api/v1/projects": {
+                "get": {
+                    "operationId": "listProjects",
+                    "responses": {
+                        "200": { "description": "Known projects",
+                            "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Project" } } } } }
+                    }
+                }
+            },
+            "/api/v1/repos": {
+                "get": {
+                    "operationId": "listRepositories",
+                    "responses": {
+                        "200": { "description": "Known repositories",
+                            "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/Repository" } } } } }
+                    }
+                }
+            },
+            "/api/v1/agents": {
+                "get": {
+                    "operationId": "listAgents",
+                    "responses": {
+                        "200": { "description": "Available agents",
+                            "content": { "application/json": { "schema": { "type": "array", "items": { "$ref": "#/components/schemas/AgentCapability" } } } } }
+                    }
+                }
+            },
+            "/api/v1/tasks": {
+                "post": {
+                    "operationId": "createTask",
+                    "requestBody": {
+                        "content": { "application/json": { "schema": { "$ref": "#/components/schemas/CreateTaskRequest" } } }
+                    },
+                    "responses": {
+                        "201": { "description": "Task created",
+                            "content": { "application/json": { "schema": { "$ref": "#/components/schemas/CreateTaskResponse" } } } },
+                        "400": { "description": "Invalid task request",
+                            "content": { "application/problem+json": { "schema": { "$ref": "#/components/schemas/ProblemDetails" } } } }
+                    }
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "DaemonInfo": { "type": "object" },
+                "ConfigureRequest": { "type": "object" },
+                "ConfigureResponse": { "type": "object" },
+                "Project": { "type": "object" },
+                "Repository": { "type": "object" },
+                "AgentCapability": { "type": "object" },
+                "CreateTaskRequest": { "type": "object" },
+                "CreateTaskResponse": { "type": "object" },
+                "ProblemDetails": { "type": "object" }
+            }
+        }
+    })
+}
+
+/// `(path, method, operationId)` triples the document describes, in the
+/// order they appear. Used by the drift test below and available to
+/// callers that want to sanity-check a document without re-parsing it.
+pub fn operations(doc: &Value) -> Vec<(String, String, String)> {
+    let mut ops = Vec::new();
+    let Some(paths) = doc.get("paths").and_then(Value::as_object) else {
+        return ops;
+    };
+    for (path, methods) in paths {
+        let Some(methods) = methods.as_object() else { continue };
+        for (method, operation) in methods {
+            let operation_id = operation
+                .get("operationId")
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string();
+            ops.push((path.clone(), method.clone(), operation_id));
+        }
+    }
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every operationId the document declares must correspond to a method
+    /// `ah_client_api::ClientApi` actually has — the manual equivalent of
+    /// the round-trip a codegen toolchain would give for free. This crate
+    /// doesn't depend on `ah-client-api` (that would be a cycle: the client
+    /// crate imports `ah-rest-api-contract`'s types), so the expected set
+    /// is restated here; keep it in sync with the trait by hand.
+    #[test]
+    fn document_operations_match_client_api_surface() {
+        let expected_operation_ids = [
+            "daemonInfo",
+            "configure",
+            "listProjects",
+            "listRepositories",
+            "listAgents",
+            "createTask",
+        ];
+
+        let doc = document();
+        let ops = operations(&doc);
+        assert_eq!(ops.len(), expected_operation_ids.len());
+        for operation_id in expected_operation_ids {
+            assert!(
+                ops.iter().any(|(_, _, id)| id == operation_id),
+                "OpenAPI document is missing operationId {operation_id:?}"
+            );
+        }
+    }
+}
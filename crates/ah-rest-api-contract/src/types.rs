@@ -494,3 +494,34 @@ pub struct LogQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub since: Option<DateTime<Utc>>,
 }
+
+/// Daemon build/version/capability info, returned by the `GET /daemon`
+/// describe endpoint so clients can detect feature support before calling
+/// into it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonInfo {
+    pub version: String,
+    #[serde(rename = "apiVersion")]
+    pub api_version: String,
+    pub build: String,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub capabilities: Vec<String>,
+}
+
+/// Runtime configuration changes accepted by the daemon's `configure`
+/// endpoint (e.g. `POST /daemon/configure`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
+pub struct ConfigureRequest {
+    #[serde(rename = "logLevel", skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<LogLevel>,
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub settings: HashMap<String, serde_json::Value>,
+}
+
+/// Response to a `configure` call.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigureResponse {
+    pub applied: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub warnings: Vec<String>,
+}
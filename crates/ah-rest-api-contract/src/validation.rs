@@ -0,0 +1,128 @@
+//! Validation helpers for API contract types: the shared schema layer that
+//! both the mock and production servers run incoming requests through, so
+//! the two can't silently drift on what counts as a valid request.
+
+use crate::error::ApiContractError;
+use crate::types::*;
+use validator::Validate;
+
+/// Validate a create task request
+pub fn validate_create_task_request(request: &CreateTaskRequest) -> Result<(), ApiContractError> {
+    request.validate()?;
+    Ok(())
+}
+
+/// Validate agent configuration
+pub fn validate_agent_config(config: &AgentConfig) -> Result<(), ApiContractError> {
+    config.validate()?;
+    Ok(())
+}
+
+/// Validate runtime configuration
+pub fn validate_runtime_config(config: &RuntimeConfig) -> Result<(), ApiContractError> {
+    config.validate()?;
+    Ok(())
+}
+
+/// Validate repository configuration
+pub fn validate_repo_config(config: &RepoConfig) -> Result<(), ApiContractError> {
+    config.validate()?;
+
+    match config.mode {
+        RepoMode::Git => {
+            if config.url.is_none() {
+                return Err(ApiContractError::Validation(
+                    validator::ValidationErrors::new(),
+                ));
+            }
+        }
+        RepoMode::Upload | RepoMode::None => {
+            // URL is optional for these modes
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate a daemon `configure` request. There's nothing to check beyond
+/// the derive-generated `#[validate(...)]` rules today, but routing it
+/// through here (rather than calling `request.validate()` directly at the
+/// call site) keeps every endpoint's validation behind the same function
+/// shape, so a future rule only needs to change in one place.
+pub fn validate_configure_request(request: &ConfigureRequest) -> Result<(), ApiContractError> {
+    request.validate()?;
+    Ok(())
+}
+
+/// Validate URL format
+pub fn validate_url(url_str: &str) -> Result<(), ApiContractError> {
+    url::Url::parse(url_str)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn valid_create_task_request() -> CreateTaskRequest {
+        CreateTaskRequest {
+            tenant_id: Some("acme".to_string()),
+            project_id: Some("storefront".to_string()),
+            prompt: "Fix the bug".to_string(),
+            repo: RepoConfig {
+                mode: RepoMode::Git,
+                url: Some("https://github.com/acme/storefront.git".parse().unwrap()),
+                branch: Some("main".to_string()),
+                commit: None,
+            },
+            runtime: RuntimeConfig {
+                runtime_type: RuntimeType::Devcontainer,
+                devcontainer_path: Some(".devcontainer/devcontainer.json".to_string()),
+                resources: None,
+            },
+            workspace: None,
+            agent: AgentConfig {
+                agent_type: "claude-code".to_string(),
+                version: "latest".to_string(),
+                settings: Default::default(),
+            },
+            delivery: None,
+            labels: Default::default(),
+            webhooks: vec![],
+        }
+    }
+
+    #[test]
+    fn test_validate_create_task_request_valid() {
+        assert!(validate_create_task_request(&valid_create_task_request()).is_ok());
+    }
+
+    #[test]
+    fn test_validate_create_task_request_empty_prompt() {
+        let mut request = valid_create_task_request();
+        request.prompt = "".to_string();
+        assert!(validate_create_task_request(&request).is_err());
+    }
+
+    #[test]
+    fn test_validate_repo_config_git_without_url() {
+        let config = RepoConfig {
+            mode: RepoMode::Git,
+            url: None,
+            branch: Some("main".to_string()),
+            commit: None,
+        };
+
+        assert!(validate_repo_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_configure_request_empty_is_valid() {
+        let request = ConfigureRequest {
+            log_level: None,
+            settings: Default::default(),
+        };
+
+        assert!(validate_configure_request(&request).is_ok());
+    }
+}
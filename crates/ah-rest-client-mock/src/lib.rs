@@ -1,36 +1,87 @@
 //! Mock REST client backed by scenarios
+//!
+//! [`MockClient`] replays a named [`scenario::ApiScenario`]: a JSON fixture
+//! file resolved from the scenario name passed to
+//! [`MockClient::from_scenario_name`], declaring the `list_*` responses to
+//! serve and a scripted sequence of `create_task` outcomes (with optional
+//! latency injection, request assertions, and HTTP-style error codes). See
+//! [`recording`] for the companion mode that captures a real [`ClientApi`]'s
+//! traffic into that same fixture format.
+
+mod recording;
+mod scenario;
 
-use async_trait::async_trait;
 use ah_client_api::{ClientApi, ClientApiError, ClientApiResult};
+use ah_core::task::TaskUpdate;
 use ah_rest_api_contract::*;
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, StreamExt};
+use scenario::ApiScenario;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub use recording::RecordingClient;
+pub use scenario::{ApiScenario as MockScenario, CreateTaskExpectation, CreateTaskFixture, FixtureResponse};
+
+/// Directory `from_scenario_name` looks for `<name>.json` fixtures in,
+/// overridable for out-of-tree test suites.
+const SCENARIOS_DIR_ENV: &str = "AH_MOCK_SCENARIOS_DIR";
+const DEFAULT_SCENARIOS_DIR: &str = "tests/fixtures/mock-scenarios";
 
 pub struct MockClient {
-    _scenario_name: String,
+    scenario_name: String,
+    scenario: ApiScenario,
+    create_task_calls: AtomicUsize,
 }
 
 impl MockClient {
+    /// Resolve `name` to `<AH_MOCK_SCENARIOS_DIR or "tests/fixtures/mock-scenarios">/<name>.json`
+    /// and load it, falling back to [`ApiScenario::demo`] wholesale when no
+    /// such file exists (so callers that only ever passed a display name,
+    /// never a fixture, keep getting the old hard-coded demo data) and
+    /// merging it over the demo data section-by-section when it does (so a
+    /// fixture can override just e.g. `create_task` without having to
+    /// restate the project/repo/agent lists).
+    ///
+    /// # Panics
+    /// Panics if the fixture file exists but fails to parse — a scenario
+    /// file is test-only input, so a malformed one should fail the test run
+    /// immediately rather than silently fall back to demo data.
     pub fn from_scenario_name(name: impl Into<String>) -> Self {
+        let scenario_name = name.into();
+        let scenario = match std::fs::read_to_string(scenario_path(&scenario_name)) {
+            Ok(json) => {
+                let file: ApiScenario = serde_json::from_str(&json).unwrap_or_else(|e| {
+                    panic!("invalid mock scenario {scenario_name:?}: {e}")
+                });
+                ApiScenario::merged_with_demo(file)
+            }
+            Err(_) => ApiScenario::demo(),
+        };
+        Self::from_scenario(scenario_name, scenario)
+    }
+
+    /// Build a mock client directly from an in-memory scenario, bypassing
+    /// file resolution — useful for unit tests that construct a
+    /// [`ApiScenario`] inline rather than reading one off disk.
+    pub fn from_scenario(scenario_name: impl Into<String>, scenario: ApiScenario) -> Self {
         Self {
-            _scenario_name: name.into(),
+            scenario_name: scenario_name.into(),
+            scenario,
+            create_task_calls: AtomicUsize::new(0),
         }
     }
 }
 
+fn scenario_path(name: &str) -> PathBuf {
+    let dir = std::env::var(SCENARIOS_DIR_ENV).unwrap_or_else(|_| DEFAULT_SCENARIOS_DIR.into());
+    PathBuf::from(dir).join(format!("{name}.json"))
+}
+
 #[async_trait]
 impl ClientApi for MockClient {
     async fn list_projects(&self, _tenant_id: Option<&str>) -> ClientApiResult<Vec<Project>> {
-        Ok(vec![
-            Project {
-                id: "p1".into(),
-                display_name: "Demo Project 1".into(),
-                last_used_at: None,
-            },
-            Project {
-                id: "p2".into(),
-                display_name: "Demo Project 2".into(),
-                last_used_at: None,
-            },
-        ])
+        Ok(self.scenario.projects.clone())
     }
 
     async fn list_repositories(
@@ -38,46 +89,74 @@ impl ClientApi for MockClient {
         _tenant_id: Option<&str>,
         _project_id: Option<&str>,
     ) -> ClientApiResult<Vec<Repository>> {
-        use url::Url;
-        Ok(vec![
-            Repository {
-                id: "r1".into(),
-                display_name: "demo/repo1".into(),
-                scm_provider: "github".into(),
-                remote_url: Url::parse("https://github.com/demo/repo1").unwrap(),
-                default_branch: "main".into(),
-                last_used_at: None,
-            },
-            Repository {
-                id: "r2".into(),
-                display_name: "demo/repo2".into(),
-                scm_provider: "github".into(),
-                remote_url: Url::parse("https://github.com/demo/repo2").unwrap(),
-                default_branch: "main".into(),
-                last_used_at: None,
-            },
-        ])
+        Ok(self.scenario.repositories.clone())
     }
 
     async fn list_agents(&self) -> ClientApiResult<Vec<AgentCapability>> {
-        Ok(vec![
-            AgentCapability {
-                agent_type: "claude-code".into(),
-                versions: vec!["latest".into()],
-                settings_schema_ref: None,
-            },
-            AgentCapability {
-                agent_type: "gpt-engineer".into(),
-                versions: vec!["v1.0".into()],
-                settings_schema_ref: None,
-            },
-        ])
+        Ok(self.scenario.agents.clone())
     }
 
     async fn create_task(
         &self,
-        _request: &CreateTaskRequest,
+        request: &CreateTaskRequest,
     ) -> ClientApiResult<CreateTaskResponse> {
-        Err(ClientApiError::Unexpected("not implemented in mock".into()))
+        let attempt = self.create_task_calls.fetch_add(1, Ordering::SeqCst);
+        let Some(fixture) = self.scenario.create_task_fixture(attempt) else {
+            return Err(ClientApiError::Unexpected(format!(
+                "mock scenario {:?} has no create_task fixture",
+                self.scenario_name
+            )));
+        };
+
+        self.scenario.check_create_task(attempt, request);
+
+        if fixture.latency_ms > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(fixture.latency_ms)).await;
+        }
+
+        match &fixture.response {
+            scenario::FixtureResponse::Ok { id, status } => Ok(CreateTaskResponse {
+                id: id.clone(),
+                status: *status,
+                links: TaskLinks {
+                    self_link: format!("/api/v1/tasks/{id}"),
+                    events: format!("/api/v1/tasks/{id}/events"),
+                    logs: format!("/api/v1/tasks/{id}/logs"),
+                },
+            }),
+            scenario::FixtureResponse::Error { status, message } if *status >= 500 => {
+                Err(ClientApiError::Server(message.clone()))
+            }
+            scenario::FixtureResponse::Error { status, message } => Err(
+                ClientApiError::Unexpected(format!("{status} {message}")),
+            ),
+        }
+    }
+
+    async fn daemon_info(&self) -> ClientApiResult<DaemonInfo> {
+        self.scenario.daemon.clone().ok_or_else(|| {
+            ClientApiError::Unexpected(format!(
+                "mock scenario {:?} has no daemon fixture",
+                self.scenario_name
+            ))
+        })
+    }
+
+    async fn configure(&self, _request: &ConfigureRequest) -> ClientApiResult<ConfigureResponse> {
+        self.scenario.configure.clone().ok_or_else(|| {
+            ClientApiError::Unexpected(format!(
+                "mock scenario {:?} has no configure fixture",
+                self.scenario_name
+            ))
+        })
+    }
+
+    async fn watch_tasks(
+        &self,
+        _tenant_id: Option<&str>,
+    ) -> ClientApiResult<BoxStream<'static, TaskUpdate>> {
+        // The mock has no task store to snapshot, so subscribers just see an
+        // empty initial view and no further updates.
+        Ok(stream::empty().boxed())
     }
 }
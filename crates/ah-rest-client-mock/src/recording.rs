@@ -0,0 +1,139 @@
+//! Recording mode: wraps a real [`ClientApi`] and captures its traffic into
+//! the same [`ApiScenario`] fixture format [`crate::MockClient`] replays, so
+//! a scenario file can be regenerated from a live run instead of
+//! hand-written.
+
+use crate::scenario::{ApiScenario, CreateTaskFixture, FixtureResponse};
+use ah_client_api::{ClientApi, ClientApiError, ClientApiResult};
+use ah_core::task::TaskUpdate;
+use ah_rest_api_contract::*;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Wraps a real `ClientApi` transparently, forwarding every call to it while
+/// accumulating an [`ApiScenario`] in memory that gets written to
+/// `output_path` on [`Self::save`] (and, as a safety net, on drop).
+pub struct RecordingClient {
+    inner: Arc<dyn ClientApi>,
+    scenario: Mutex<ApiScenario>,
+    output_path: PathBuf,
+}
+
+impl RecordingClient {
+    pub fn wrap(inner: Arc<dyn ClientApi>, output_path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            scenario: Mutex::new(ApiScenario::default()),
+            output_path: output_path.into(),
+        }
+    }
+
+    /// Write everything observed so far to `output_path` as pretty JSON, in
+    /// the same format [`MockClient::from_scenario_name`] reads back.
+    ///
+    /// [`MockClient::from_scenario_name`]: crate::MockClient::from_scenario_name
+    pub fn save(&self) -> std::io::Result<()> {
+        let scenario = self.scenario.lock().unwrap();
+        let json =
+            serde_json::to_string_pretty(&*scenario).expect("ApiScenario always serializes");
+        if let Some(parent) = self.output_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.output_path, json)
+    }
+}
+
+impl Drop for RecordingClient {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            eprintln!(
+                "warning: failed to write recorded mock scenario to {}: {}",
+                self.output_path.display(),
+                e
+            );
+        }
+    }
+}
+
+#[async_trait]
+impl ClientApi for RecordingClient {
+    async fn list_projects(&self, tenant_id: Option<&str>) -> ClientApiResult<Vec<Project>> {
+        let projects = self.inner.list_projects(tenant_id).await?;
+        self.scenario.lock().unwrap().projects = projects.clone();
+        Ok(projects)
+    }
+
+    async fn list_repositories(
+        &self,
+        tenant_id: Option<&str>,
+        project_id: Option<&str>,
+    ) -> ClientApiResult<Vec<Repository>> {
+        let repositories = self.inner.list_repositories(tenant_id, project_id).await?;
+        self.scenario.lock().unwrap().repositories = repositories.clone();
+        Ok(repositories)
+    }
+
+    async fn list_agents(&self) -> ClientApiResult<Vec<AgentCapability>> {
+        let agents = self.inner.list_agents().await?;
+        self.scenario.lock().unwrap().agents = agents.clone();
+        Ok(agents)
+    }
+
+    async fn create_task(
+        &self,
+        request: &CreateTaskRequest,
+    ) -> ClientApiResult<CreateTaskResponse> {
+        let result = self.inner.create_task(request).await;
+
+        let response = match &result {
+            Ok(response) => FixtureResponse::Ok {
+                id: response.id.clone(),
+                status: response.status,
+            },
+            Err(ClientApiError::Server(message)) => FixtureResponse::Error {
+                status: 500,
+                message: message.clone(),
+            },
+            // `ClientApiError` has no status code of its own for this
+            // variant; record it as 0 rather than guess one, since a
+            // replayed fixture only needs to reproduce the `Unexpected`
+            // branch, not a specific HTTP code.
+            Err(ClientApiError::Unexpected(message)) => FixtureResponse::Error {
+                status: 0,
+                message: message.clone(),
+            },
+        };
+        self.scenario.lock().unwrap().create_task.push(CreateTaskFixture {
+            expect: None,
+            latency_ms: 0,
+            response,
+        });
+
+        result
+    }
+
+    async fn daemon_info(&self) -> ClientApiResult<DaemonInfo> {
+        let info = self.inner.daemon_info().await?;
+        self.scenario.lock().unwrap().daemon = Some(info.clone());
+        Ok(info)
+    }
+
+    async fn configure(&self, request: &ConfigureRequest) -> ClientApiResult<ConfigureResponse> {
+        let response = self.inner.configure(request).await?;
+        self.scenario.lock().unwrap().configure = Some(response.clone());
+        Ok(response)
+    }
+
+    async fn watch_tasks(
+        &self,
+        tenant_id: Option<&str>,
+    ) -> ClientApiResult<BoxStream<'static, TaskUpdate>> {
+        // Not captured into the scenario file: `MockClient`'s replay side
+        // doesn't support scripted `watch_tasks` streams either (it always
+        // returns an empty stream), so there's nothing meaningful to record
+        // here yet.
+        self.inner.watch_tasks(tenant_id).await
+    }
+}
@@ -0,0 +1,206 @@
+//! JSON fixture format replayed by [`crate::MockClient`].
+//!
+//! A scenario file declares the `list_projects`/`list_repositories`/
+//! `list_agents` responses to serve and a sequence of `create_task`
+//! fixtures, each of which can inject latency, assert on the incoming
+//! [`CreateTaskRequest`], and resolve to either a success response or an
+//! HTTP-style error that gets mapped onto [`ClientApiError`].
+//!
+//! Any section omitted from the file falls back to [`ApiScenario::demo`]'s
+//! hard-coded data, so existing scenario names with no fixture file on disk
+//! (or a fixture that only overrides part of the picture) keep working.
+
+use ah_rest_api_contract::{
+    AgentCapability, ConfigureResponse, CreateTaskRequest, DaemonInfo, Project, Repository,
+    SessionStatus,
+};
+use serde::{Deserialize, Serialize};
+
+/// Full fixture set for a single named scenario.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiScenario {
+    pub projects: Vec<Project>,
+    pub repositories: Vec<Repository>,
+    pub agents: Vec<AgentCapability>,
+    /// `create_task` responses, consumed in order as calls come in. The
+    /// last entry repeats for any call past the end of the list.
+    pub create_task: Vec<CreateTaskFixture>,
+    /// `daemon_info` response. Falls back to [`ApiScenario::demo`]'s value
+    /// like every other section.
+    pub daemon: Option<DaemonInfo>,
+    /// `configure` response, returned for every call (configure isn't
+    /// scripted as a sequence — it's expected to be idempotent).
+    pub configure: Option<ConfigureResponse>,
+}
+
+/// One scripted `create_task` call: an optional assertion on the request,
+/// optional injected latency, and the response to return.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTaskFixture {
+    #[serde(default)]
+    pub expect: Option<CreateTaskExpectation>,
+    /// Milliseconds to sleep before responding, simulating network/queueing
+    /// latency.
+    #[serde(default)]
+    pub latency_ms: u64,
+    pub response: FixtureResponse,
+}
+
+/// Assertions checked against an incoming [`CreateTaskRequest`] before the
+/// fixture's response is returned. Fields left `None` are not checked.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CreateTaskExpectation {
+    pub prompt_contains: Option<String>,
+    pub project_id: Option<String>,
+}
+
+impl CreateTaskExpectation {
+    /// Panics with a description of the mismatch, mirroring the assertion
+    /// style of `ah-client-api-test`'s harness: a scenario file is test-only
+    /// fixture data, so a violated expectation should fail loudly rather
+    /// than be swallowed into a `ClientApiResult`.
+    fn check(&self, request: &CreateTaskRequest) {
+        if let Some(expected) = &self.prompt_contains {
+            assert!(
+                request.prompt.contains(expected.as_str()),
+                "create_task prompt {:?} does not contain expected substring {:?}",
+                request.prompt,
+                expected
+            );
+        }
+        if let Some(expected) = &self.project_id {
+            assert_eq!(
+                request.project_id.as_deref(),
+                Some(expected.as_str()),
+                "create_task project_id mismatch"
+            );
+        }
+    }
+}
+
+/// A fixture's outcome: either a successful task creation or an HTTP-style
+/// error to translate into a [`ah_client_api::ClientApiError`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "lowercase")]
+pub enum FixtureResponse {
+    Ok {
+        id: String,
+        #[serde(default = "default_status")]
+        status: SessionStatus,
+    },
+    Error {
+        /// HTTP-style status code. `>= 500` maps to `ClientApiError::Server`,
+        /// anything else to `ClientApiError::Unexpected` — `ah-client-api`
+        /// doesn't carry a status code of its own, so the code only selects
+        /// the variant.
+        status: u16,
+        message: String,
+    },
+}
+
+fn default_status() -> SessionStatus {
+    SessionStatus::Queued
+}
+
+impl ApiScenario {
+    /// The hard-coded fixture data `MockClient` served before scenario
+    /// files existed, kept as the fallback for scenario names with no file
+    /// on disk and as a base for sections a fixture file leaves empty.
+    pub fn demo() -> Self {
+        use url::Url;
+
+        Self {
+            projects: vec![
+                Project {
+                    id: "p1".into(),
+                    display_name: "Demo Project 1".into(),
+                    last_used_at: None,
+                },
+                Project {
+                    id: "p2".into(),
+                    display_name: "Demo Project 2".into(),
+                    last_used_at: None,
+                },
+            ],
+            repositories: vec![
+                Repository {
+                    id: "r1".into(),
+                    display_name: "demo/repo1".into(),
+                    scm_provider: "github".into(),
+                    remote_url: Url::parse("https://github.com/demo/repo1").unwrap(),
+                    default_branch: "main".into(),
+                    last_used_at: None,
+                },
+                Repository {
+                    id: "r2".into(),
+                    display_name: "demo/repo2".into(),
+                    scm_provider: "github".into(),
+                    remote_url: Url::parse("https://github.com/demo/repo2").unwrap(),
+                    default_branch: "main".into(),
+                    last_used_at: None,
+                },
+            ],
+            agents: vec![
+                AgentCapability {
+                    agent_type: "claude-code".into(),
+                    versions: vec!["latest".into()],
+                    settings_schema_ref: None,
+                },
+                AgentCapability {
+                    agent_type: "gpt-engineer".into(),
+                    versions: vec!["v1.0".into()],
+                    settings_schema_ref: None,
+                },
+            ],
+            create_task: Vec::new(),
+            daemon: Some(DaemonInfo {
+                version: "0.0.0-mock".into(),
+                api_version: "v1".into(),
+                build: "mock".into(),
+                capabilities: vec!["tasks".into(), "projects".into(), "repositories".into()],
+            }),
+            configure: Some(ConfigureResponse {
+                applied: true,
+                warnings: Vec::new(),
+            }),
+        }
+    }
+
+    /// Merge `file` on top of [`Self::demo`]: any section `file` left empty
+    /// (the default for a field a scenario author didn't write) keeps the
+    /// demo data instead of serving nothing.
+    pub(crate) fn merged_with_demo(file: Self) -> Self {
+        let demo = Self::demo();
+        Self {
+            projects: if file.projects.is_empty() { demo.projects } else { file.projects },
+            repositories: if file.repositories.is_empty() {
+                demo.repositories
+            } else {
+                file.repositories
+            },
+            agents: if file.agents.is_empty() { demo.agents } else { file.agents },
+            create_task: file.create_task,
+            daemon: file.daemon.or(demo.daemon),
+            configure: file.configure.or(demo.configure),
+        }
+    }
+
+    pub(crate) fn check_create_task(&self, attempt: usize, request: &CreateTaskRequest) {
+        let fixture = self.create_task_fixture(attempt);
+        if let Some(fixture) = fixture {
+            if let Some(expect) = &fixture.expect {
+                expect.check(request);
+            }
+        }
+    }
+
+    pub(crate) fn create_task_fixture(&self, attempt: usize) -> Option<&CreateTaskFixture> {
+        if self.create_task.is_empty() {
+            return None;
+        }
+        let index = attempt.min(self.create_task.len() - 1);
+        self.create_task.get(index)
+    }
+}
@@ -7,7 +7,7 @@ use url::Url;
 
 use crate::auth::AuthConfig;
 use crate::error::{RestClientError, RestClientResult};
-use crate::sse::SessionEventStream;
+use crate::sse::{SessionEventStream, TaskUpdateStream};
 
 /// REST API client for agent-harbor service
 #[derive(Debug, Clone)]
@@ -133,6 +133,14 @@ impl RestClient {
         SessionEventStream::connect(&self.base_url, session_id, &self.auth).await
     }
 
+    /// Stream live task updates via SSE
+    pub async fn stream_task_updates(
+        &self,
+        tenant_id: Option<&str>,
+    ) -> RestClientResult<TaskUpdateStream> {
+        TaskUpdateStream::connect(&self.base_url, tenant_id, &self.auth).await
+    }
+
     /// Get session info (fleet and endpoints)
     pub async fn get_session_info(
         &self,
@@ -172,6 +180,16 @@ impl RestClient {
         Ok(response.items)
     }
 
+    /// Describe the daemon's build/version/capabilities
+    pub async fn daemon_info(&self) -> RestClientResult<DaemonInfo> {
+        self.get("/api/v1/daemon").await
+    }
+
+    /// Apply a runtime configuration change
+    pub async fn configure(&self, request: &ConfigureRequest) -> RestClientResult<ConfigureResponse> {
+        self.post("/api/v1/daemon/configure", request).await
+    }
+
     /// List projects
     pub async fn list_projects(&self, tenant_id: Option<&str>) -> RestClientResult<Vec<Project>> {
         let mut url = "/api/v1/projects".to_string();
@@ -15,7 +15,9 @@ pub use error::*;
 
 use async_trait::async_trait;
 use ah_client_api::{ClientApi, ClientApiError, ClientApiResult};
+use ah_core::task::TaskUpdate;
 use ah_rest_api_contract::*;
+use futures::stream::{BoxStream, StreamExt};
 
 #[async_trait]
 impl ClientApi for client::RestClient {
@@ -47,4 +49,26 @@ impl ClientApi for client::RestClient {
             .await
             .map_err(|e| ClientApiError::Server(e.to_string()))
     }
+
+    async fn daemon_info(&self) -> ClientApiResult<DaemonInfo> {
+        self.daemon_info().await.map_err(|e| ClientApiError::Server(e.to_string()))
+    }
+
+    async fn configure(&self, request: &ConfigureRequest) -> ClientApiResult<ConfigureResponse> {
+        self.configure(request)
+            .await
+            .map_err(|e| ClientApiError::Server(e.to_string()))
+    }
+
+    async fn watch_tasks(
+        &self,
+        tenant_id: Option<&str>,
+    ) -> ClientApiResult<BoxStream<'static, TaskUpdate>> {
+        let stream = self
+            .stream_task_updates(tenant_id)
+            .await
+            .map_err(|e| ClientApiError::Server(e.to_string()))?;
+
+        Ok(stream.filter_map(|item| async move { item.ok() }).boxed())
+    }
 }
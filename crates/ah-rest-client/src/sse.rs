@@ -1,5 +1,6 @@
 //! Server-Sent Events (SSE) streaming support
 
+use ah_core::task::TaskUpdate;
 use ah_rest_api_contract::SessionEvent;
 use futures::stream::Stream;
 use std::pin::Pin;
@@ -46,6 +47,43 @@ impl Stream for SessionEventStream {
     }
 }
 
+/// SSE stream of [`TaskUpdate`]s for the task list view.
+pub struct TaskUpdateStream {
+    receiver: mpsc::Receiver<Result<TaskUpdate, RestClientError>>,
+    _handle: tokio::task::JoinHandle<()>,
+}
+
+impl TaskUpdateStream {
+    /// Connect to the task-watch endpoint for `tenant_id`.
+    pub async fn connect(
+        _base_url: &url::Url,
+        _tenant_id: Option<&str>,
+        _auth: &AuthConfig,
+    ) -> RestClientResult<Self> {
+        // TODO: Implement proper SSE streaming with eventsource-client
+        // For now, return a placeholder that never yields events.
+        let (_tx, rx) = mpsc::channel(32);
+
+        let handle = tokio::spawn(async {
+            // Placeholder - keep the task alive
+            tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+        });
+
+        Ok(TaskUpdateStream {
+            receiver: rx,
+            _handle: handle,
+        })
+    }
+}
+
+impl Stream for TaskUpdateStream {
+    type Item = Result<TaskUpdate, RestClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
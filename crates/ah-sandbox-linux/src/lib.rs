@@ -1,54 +1,107 @@
 //! Linux-specific sandbox implementation for Agents Workflow.
+//!
+//! [`LinuxNamespaceProvider`] isolates the sandboxed command in fresh
+//! user/mount/PID/network namespaces, pivots its root filesystem into
+//! `SandboxConfig::rootfs`, and enforces `SandboxConfig::resource_limits`
+//! through a dedicated cgroup v2 subtree.
 
+use ah_sandbox::{Error, MountBinding, Result, ResourceLimits, SandboxConfig, SandboxProvider, SandboxResult};
 use async_trait::async_trait;
-use ah_sandbox::{Result, SandboxConfig, SandboxProvider, SandboxResult};
+use std::fs;
 use std::os::unix::process::CommandExt;
+use std::path::{Path, PathBuf};
 use tokio::process::Command;
 
-/// Linux sandbox provider using namespaces.
+/// Get the best available sandbox provider for the current platform.
+pub fn default_provider() -> Result<Box<dyn SandboxProvider>> {
+    if LinuxNamespaceProvider::is_available() {
+        Ok(Box::new(LinuxNamespaceProvider::new()))
+    } else {
+        Err(Error::NoAvailableProvider)
+    }
+}
+
+/// Linux sandbox provider backed by user/mount/PID/net namespaces and a
+/// cgroup v2 subtree for resource limits.
 #[derive(Default)]
-pub struct LinuxSandboxProvider;
+pub struct LinuxNamespaceProvider;
 
-impl LinuxSandboxProvider {
-    /// Create a new Linux sandbox provider.
+impl LinuxNamespaceProvider {
+    /// Create a new Linux namespace sandbox provider.
     pub fn new() -> Self {
         Self
     }
 
-    /// Check if Linux namespaces are available on this system.
+    /// Check whether unprivileged user namespaces and a cgroup v2 mount are
+    /// both available, which is everything this provider needs.
     pub fn is_available() -> bool {
-        // Check if we're on Linux and have the necessary capabilities
-        cfg!(target_os = "linux")
+        if !cfg!(target_os = "linux") {
+            return false;
+        }
+
+        unprivileged_userns_supported() && Path::new("/sys/fs/cgroup/cgroup.controllers").exists()
+    }
+}
+
+/// Probe for unprivileged user-namespace support via the sysctl most distros
+/// expose (`/proc/sys/kernel/unprivileged_userns_clone`). When the sysctl is
+/// absent, the kernel doesn't gate user namespaces behind it, so treat that
+/// as supported.
+fn unprivileged_userns_supported() -> bool {
+    match fs::read_to_string("/proc/sys/kernel/unprivileged_userns_clone") {
+        Ok(contents) => contents.trim() == "1",
+        Err(_) => true,
     }
 }
 
 #[async_trait]
-impl SandboxProvider for LinuxSandboxProvider {
+impl SandboxProvider for LinuxNamespaceProvider {
     async fn execute(&self, config: &SandboxConfig) -> Result<SandboxResult> {
-        // Placeholder implementation
-        // In a real implementation, this would:
-        // 1. Set up Linux namespaces (user, mount, network, etc.)
-        // 2. Configure seccomp filters
-        // 3. Set up cgroups for resource limits
-        // 4. Execute the command in the sandbox
-
-        // For now, just execute the command directly
         if config.command.is_empty() {
-            return Err(ah_sandbox::Error::execution("No command specified"));
+            return Err(Error::execution("No command specified"));
         }
 
-        let mut cmd = Command::new(&config.command[0]);
-        cmd.args(&config.command[1..]);
+        let cgroup = SandboxCgroup::create(config.resource_limits.as_ref())?;
 
-        if let Some(workdir) = &config.working_dir {
-            cmd.current_dir(workdir);
-        }
+        let rootfs = config.rootfs.clone();
+        let mounts = config.mounts.clone();
+        let working_dir = config.working_dir.clone();
+        let env = config.env.clone();
+        let command = config.command.clone();
+        let cgroup_procs_path = cgroup.procs_path();
 
-        for (key, value) in &config.env {
+        let mut cmd = Command::new(&command[0]);
+        cmd.args(&command[1..]);
+        cmd.env_clear();
+        for (key, value) in &env {
             cmd.env(key, value);
         }
 
+        // Join the cgroup first, while this is still the plain forked child
+        // in the host user/mount namespaces: the host `/sys/fs/cgroup` path
+        // is reachable and writable here, and `std::process::id()` still
+        // names the host TGID the cgroup tracks. Once `enter_namespaces`
+        // unshares user/mount/PID namespaces and `pivot_into_rootfs` makes
+        // the sandbox rootfs `/`, neither is true any more. Then enter fresh
+        // user/mount/PID/net namespaces and pivot into the sandbox rootfs
+        // before the target binary runs.
+        unsafe {
+            cmd.pre_exec(move || {
+                if let Some(procs_path) = &cgroup_procs_path {
+                    join_cgroup(procs_path)?;
+                }
+                enter_namespaces()?;
+                apply_mounts(&rootfs, &mounts)?;
+                pivot_into_rootfs(&rootfs)?;
+                if let Some(dir) = &working_dir {
+                    std::env::set_current_dir(dir)?;
+                }
+                Ok(())
+            });
+        }
+
         let output = cmd.output().await?;
+        cgroup.teardown();
 
         Ok(SandboxResult {
             exit_code: output.status.code().unwrap_or(-1),
@@ -60,3 +113,235 @@ impl SandboxProvider for LinuxSandboxProvider {
         Self::is_available()
     }
 }
+
+/// Enter fresh user, mount, PID, and network namespaces via `unshare(2)`.
+/// Runs in the child after `fork` (from `Command::pre_exec`), so only
+/// async-signal-safe calls are allowed here; `unshare`, `fork`, `waitpid`
+/// and `_exit` all are.
+///
+/// `unshare(CLONE_NEWPID)` only changes the PID namespace of *future*
+/// children of the calling process; the caller itself stays in its
+/// original namespace. So after unsharing, fork once more: the new child
+/// becomes PID 1 of the fresh PID namespace and goes on to `execvp` the
+/// target command, while this process waits for it and relays its exit
+/// status in place of calling `exec` itself.
+fn enter_namespaces() -> std::io::Result<()> {
+    let flags = libc::CLONE_NEWUSER | libc::CLONE_NEWNS | libc::CLONE_NEWPID | libc::CLONE_NEWNET;
+    if unsafe { libc::unshare(flags) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    match unsafe { libc::fork() } {
+        -1 => Err(std::io::Error::last_os_error()),
+        0 => Ok(()),
+        pid => {
+            let mut status: libc::c_int = 0;
+            loop {
+                if unsafe { libc::waitpid(pid, &mut status, 0) } >= 0 {
+                    break;
+                }
+                let err = std::io::Error::last_os_error();
+                if err.kind() != std::io::ErrorKind::Interrupted {
+                    return Err(err);
+                }
+            }
+            let code = if libc::WIFEXITED(status) { libc::WEXITSTATUS(status) } else { 128 + libc::WTERMSIG(status) };
+            unsafe { libc::_exit(code) };
+        }
+    }
+}
+
+/// Bind-mount each configured [`MountBinding`] into the (not yet pivoted)
+/// sandbox rootfs.
+fn apply_mounts(rootfs: &Path, mounts: &[MountBinding]) -> std::io::Result<()> {
+    for mount in mounts {
+        let target = rootfs.join(&mount.sandbox_path);
+        if target.is_file() || !mount.host_path.is_dir() {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::File::create(&target).ok();
+        } else {
+            fs::create_dir_all(&target)?;
+        }
+
+        bind_mount(&mount.host_path, &target, mount.read_only)?;
+    }
+    Ok(())
+}
+
+fn bind_mount(source: &Path, target: &Path, read_only: bool) -> std::io::Result<()> {
+    use std::ffi::CString;
+
+    let source_c = CString::new(source.as_os_str().to_str().unwrap_or_default())?;
+    let target_c = CString::new(target.as_os_str().to_str().unwrap_or_default())?;
+
+    let rc = unsafe {
+        libc::mount(
+            source_c.as_ptr(),
+            target_c.as_ptr(),
+            std::ptr::null(),
+            libc::MS_BIND | libc::MS_REC,
+            std::ptr::null(),
+        )
+    };
+    if rc != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    if read_only {
+        let rc = unsafe {
+            libc::mount(
+                std::ptr::null(),
+                target_c.as_ptr(),
+                std::ptr::null(),
+                libc::MS_BIND | libc::MS_REMOUNT | libc::MS_RDONLY,
+                std::ptr::null(),
+            )
+        };
+        if rc != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
+/// `pivot_root(2)` into `rootfs`, making it the process's new `/`.
+fn pivot_into_rootfs(rootfs: &Path) -> std::io::Result<()> {
+    use std::ffi::CString;
+
+    let new_root_c = CString::new(rootfs.as_os_str().to_str().unwrap_or_default())?;
+
+    // `pivot_root(2)` requires `new_root` to be a mount point. `rootfs` is
+    // typically an ordinary directory, so bind-mount it onto itself first,
+    // then mark that mount private so pivoting doesn't propagate back to
+    // the host's mount namespace.
+    if unsafe {
+        libc::mount(new_root_c.as_ptr(), new_root_c.as_ptr(), std::ptr::null(), libc::MS_BIND | libc::MS_REC, std::ptr::null())
+    } != 0
+    {
+        return Err(std::io::Error::last_os_error());
+    }
+    if unsafe {
+        libc::mount(std::ptr::null(), new_root_c.as_ptr(), std::ptr::null(), libc::MS_PRIVATE | libc::MS_REC, std::ptr::null())
+    } != 0
+    {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let old_root = rootfs.join(".old_root");
+    fs::create_dir_all(&old_root)?;
+    let old_root_c = CString::new(old_root.as_os_str().to_str().unwrap_or_default())?;
+
+    if unsafe { libc::syscall(libc::SYS_pivot_root, new_root_c.as_ptr(), old_root_c.as_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    std::env::set_current_dir("/")?;
+
+    let old_root_c = CString::new("/.old_root")?;
+    if unsafe { libc::umount2(old_root_c.as_ptr(), libc::MNT_DETACH) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    fs::remove_dir("/.old_root").ok();
+
+    Ok(())
+}
+
+fn join_cgroup(procs_path: &Path) -> std::io::Result<()> {
+    fs::write(procs_path, format!("{}", std::process::id()))
+}
+
+/// Enable the controllers our limits need in the root cgroup's
+/// `cgroup.subtree_control`, so child cgroups (like the one `create`s
+/// below) are actually allowed to set `memory.max`/`cpu.max`/`pids.max`.
+/// Without this, those files don't exist in a freshly created subtree and
+/// writing to them fails with `ENOENT`.
+fn enable_controllers(limits: &ResourceLimits) -> Result<()> {
+    let mut wanted = Vec::new();
+    if limits.memory_bytes.is_some() {
+        wanted.push("memory");
+    }
+    if limits.cpu_cores.is_some() {
+        wanted.push("cpu");
+    }
+    if limits.max_pids.is_some() {
+        wanted.push("pids");
+    }
+    if wanted.is_empty() {
+        return Ok(());
+    }
+
+    let available = fs::read_to_string("/sys/fs/cgroup/cgroup.controllers")
+        .map_err(|e| Error::cgroup(format!("failed to read cgroup.controllers: {}", e)))?;
+    let available: Vec<&str> = available.split_whitespace().collect();
+
+    let enable =
+        wanted.iter().filter(|c| available.contains(c)).map(|c| format!("+{}", c)).collect::<Vec<_>>().join(" ");
+    if enable.is_empty() {
+        return Ok(());
+    }
+
+    // Already-enabled controllers are a harmless no-op to re-enable, so no
+    // need to read back the current subtree_control contents first.
+    fs::write("/sys/fs/cgroup/cgroup.subtree_control", enable)
+        .map_err(|e| Error::cgroup(format!("failed to enable controllers in cgroup.subtree_control: {}", e)))
+}
+
+/// A dedicated cgroup v2 subtree created for one sandboxed process, with
+/// memory/CPU/pids limits applied before the child joins it. Removed once
+/// the process has exited.
+struct SandboxCgroup {
+    path: Option<PathBuf>,
+}
+
+impl SandboxCgroup {
+    /// Create (and configure, if limits are given) a fresh cgroup under
+    /// `/sys/fs/cgroup/ah-sandbox-<pid>`. Returns a no-op handle when no
+    /// limits were requested, so unconfigured sandboxes don't pay the cost
+    /// of a cgroup subtree at all.
+    fn create(limits: Option<&ResourceLimits>) -> Result<Self> {
+        let Some(limits) = limits else {
+            return Ok(Self { path: None });
+        };
+
+        enable_controllers(limits)?;
+
+        let path = PathBuf::from(format!("/sys/fs/cgroup/ah-sandbox-{}", std::process::id()));
+        fs::create_dir_all(&path)
+            .map_err(|e| Error::cgroup(format!("failed to create cgroup at {}: {}", path.display(), e)))?;
+
+        if let Some(memory_bytes) = limits.memory_bytes {
+            fs::write(path.join("memory.max"), memory_bytes.to_string())
+                .map_err(|e| Error::cgroup(format!("failed to set memory.max: {}", e)))?;
+        }
+        if let Some(cpu_cores) = limits.cpu_cores {
+            let period = 100_000u64;
+            let quota = (cpu_cores * period as f64) as u64;
+            fs::write(path.join("cpu.max"), format!("{} {}", quota, period))
+                .map_err(|e| Error::cgroup(format!("failed to set cpu.max: {}", e)))?;
+        }
+        if let Some(max_pids) = limits.max_pids {
+            fs::write(path.join("pids.max"), max_pids.to_string())
+                .map_err(|e| Error::cgroup(format!("failed to set pids.max: {}", e)))?;
+        }
+
+        Ok(Self { path: Some(path) })
+    }
+
+    /// Path of the `cgroup.procs` file the sandboxed process should join,
+    /// or `None` when no limits were requested and there's no cgroup to
+    /// join.
+    fn procs_path(&self) -> Option<PathBuf> {
+        self.path.as_ref().map(|p| p.join("cgroup.procs"))
+    }
+
+    /// Remove the cgroup subtree. Best-effort: the kernel only allows
+    /// removal once it's empty, which is true once the child has exited.
+    fn teardown(self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_dir(path);
+        }
+    }
+}
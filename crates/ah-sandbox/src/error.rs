@@ -0,0 +1,47 @@
+//! Error types for sandbox operations.
+
+/// Error type for sandbox operations.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// No sandbox provider is available on the current platform.
+    #[error("no sandbox provider available on this platform")]
+    NoAvailableProvider,
+
+    /// The sandboxed command failed to execute or exited abnormally.
+    #[error("sandbox execution error: {message}")]
+    Execution { message: String },
+
+    /// Setting up namespaces (user/mount/pid/net) for the sandbox failed.
+    #[error("namespace setup error: {message}")]
+    Namespace { message: String },
+
+    /// Creating or configuring the cgroup for the sandboxed process failed.
+    #[error("cgroup setup error: {message}")]
+    Cgroup { message: String },
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl Error {
+    /// Create a new execution-related error.
+    pub fn execution<S: Into<String>>(message: S) -> Self {
+        Self::Execution {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new namespace-setup error.
+    pub fn namespace<S: Into<String>>(message: S) -> Self {
+        Self::Namespace {
+            message: message.into(),
+        }
+    }
+
+    /// Create a new cgroup-setup error.
+    pub fn cgroup<S: Into<String>>(message: S) -> Self {
+        Self::Cgroup {
+            message: message.into(),
+        }
+    }
+}
@@ -24,6 +24,34 @@ pub struct SandboxConfig {
     pub env: Vec<(String, String)>,
     /// Command and arguments to execute.
     pub command: Vec<String>,
+    /// Additional paths to bind-mount into the sandbox, from host path to
+    /// sandbox-relative path.
+    pub mounts: Vec<MountBinding>,
+    /// Resource limits to apply to the sandboxed process, if any.
+    pub resource_limits: Option<ResourceLimits>,
+}
+
+/// A single bind mount from the host into the sandbox root.
+#[derive(Debug, Clone)]
+pub struct MountBinding {
+    /// Path on the host.
+    pub host_path: std::path::PathBuf,
+    /// Path inside the sandbox, relative to `rootfs`.
+    pub sandbox_path: std::path::PathBuf,
+    /// Whether the bind mount should be read-only.
+    pub read_only: bool,
+}
+
+/// OCI-style resource limits enforced via a cgroup v2 subtree.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceLimits {
+    /// Memory limit in bytes (`memory.max`).
+    pub memory_bytes: Option<u64>,
+    /// CPU quota as a fraction of one core (e.g. `1.5` = 150%), translated
+    /// into `cpu.max`.
+    pub cpu_cores: Option<f64>,
+    /// Maximum number of processes/threads (`pids.max`).
+    pub max_pids: Option<u64>,
 }
 
 /// Result of sandbox execution.
@@ -48,8 +76,12 @@ pub trait SandboxProvider: Send + Sync {
 }
 
 /// Get the best available sandbox provider for the current platform.
+///
+/// This crate only defines the [`SandboxProvider`] trait and has no
+/// dependency on platform-specific implementations, so it cannot construct
+/// one itself. Platform crates (e.g. `ah-sandbox-linux`) expose their own
+/// `default_provider()` that callers should prefer; this is kept only as a
+/// safe fallback for code that depends solely on `ah-sandbox`.
 pub fn default_provider() -> Result<Box<dyn SandboxProvider>> {
-    // For now, return an error as sandbox providers need to be integrated
-    // TODO: Implement proper provider detection when subcrates are integrated
     Err(Error::NoAvailableProvider)
 }
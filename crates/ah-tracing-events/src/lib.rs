@@ -0,0 +1,140 @@
+//! A `tracing_subscriber::Layer` that streams structured spans/events into
+//! the `events` table, giving users a persistent, queryable activity
+//! timeline per session instead of ephemeral stderr logs.
+
+use ah_core::DatabaseManager;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicI64, Ordering};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+thread_local! {
+    /// Stack of session IDs attached via [`attach_session`] on this thread,
+    /// innermost (most recently entered) last.
+    static SESSION_STACK: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// RAII guard that makes `session_id` the active session for any event
+/// recorded on this thread until dropped. Supports nesting (e.g. a sandbox
+/// run started from within an already-attached agent session).
+///
+/// Note: like any `thread_local`-based context, this only reflects the
+/// active session for code that doesn't hop threads across an `.await`
+/// point (true by default under the current-thread runtime, and true in
+/// practice for the synchronous spans of work this guard is meant to
+/// bracket).
+pub struct SessionGuard {
+    _private: (),
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        SESSION_STACK.with(|stack| {
+            stack.borrow_mut().pop();
+        });
+    }
+}
+
+/// Attach `session_id` as the current session for events recorded on this
+/// thread until the returned guard is dropped.
+pub fn attach_session(session_id: impl Into<String>) -> SessionGuard {
+    SESSION_STACK.with(|stack| stack.borrow_mut().push(session_id.into()));
+    SessionGuard { _private: () }
+}
+
+fn current_session() -> Option<String> {
+    SESSION_STACK.with(|stack| stack.borrow().last().cloned())
+}
+
+/// Monotonically increasing nanosecond-precision timestamp, so events
+/// recorded in quick succession (or across a clock step-back) still sort in
+/// the order they were actually observed.
+static LAST_TS_NANOS: AtomicI64 = AtomicI64::new(0);
+
+fn monotonic_timestamp() -> String {
+    let wall_clock_nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0);
+    let ts_nanos = LAST_TS_NANOS
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| Some(wall_clock_nanos.max(last + 1)))
+        .unwrap_or(wall_clock_nanos);
+
+    chrono::DateTime::from_timestamp(ts_nanos / 1_000_000_000, (ts_nanos % 1_000_000_000) as u32)
+        .unwrap_or_else(chrono::Utc::now)
+        .to_rfc3339()
+}
+
+/// Collects a tracing event's fields into a JSON object.
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), Value::from(format!("{:?}", value)));
+    }
+}
+
+/// A `tracing_subscriber::Layer` that inserts one row per event into the
+/// `events` table: `type` from an explicit `event_type` field (falling back
+/// to the event's target), `data` as the serialized JSON of its remaining
+/// fields, and `ts` from [`monotonic_timestamp`]. Events with no session
+/// attached via [`attach_session`] (and no explicit `session_id` field) are
+/// dropped, since `events.session_id` is required.
+pub struct EventsLayer {
+    db: DatabaseManager,
+}
+
+impl EventsLayer {
+    /// Create a new layer writing into the database behind `db`.
+    pub fn new(db: DatabaseManager) -> Self {
+        Self { db }
+    }
+}
+
+impl<S> Layer<S> for EventsLayer
+where
+    S: Subscriber,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = Map::new();
+        event.record(&mut JsonVisitor(&mut fields));
+
+        let session_id = fields
+            .remove("session_id")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .or_else(current_session);
+        let Some(session_id) = session_id else {
+            return;
+        };
+
+        let event_type = fields
+            .remove("event_type")
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| event.metadata().target().to_string());
+
+        let data = serde_json::to_string(&fields).ok();
+        let ts = monotonic_timestamp();
+
+        let _ = self.db.record_event(&session_id, &ts, &event_type, data.as_deref());
+    }
+}
@@ -0,0 +1,119 @@
+//! eBPF programs for `sandbox-audit`: observe syscalls and capability checks made by
+//! the sandboxed workload without blocking any of them.
+//!
+//! This crate is the `aya-ebpf` guest half of `sandbox-audit` - it is compiled to BPF
+//! bytecode and loaded into the kernel by `sandbox_audit::AuditManager::start()`. It
+//! never makes enforcement decisions; it only reports what happened.
+
+#![no_std]
+#![no_main]
+
+use aya_ebpf::{
+    cty::c_long,
+    helpers::{bpf_get_current_cgroup_id, bpf_get_current_pid_tgid},
+    macros::{kprobe, map, tracepoint},
+    maps::PerfEventArray,
+    programs::{ProbeContext, TracePointContext},
+};
+
+/// One audited event: either a syscall entry or a `cap_capable()` check.
+///
+/// Mirrors `sandbox_audit::AuditEvent` - keep the two in sync, since the host side
+/// reinterprets these raw bytes from the perf buffer.
+#[repr(C)]
+pub struct RawAuditEvent {
+    pub pid: u32,
+    pub cgroup_id: u64,
+    /// Syscall number, or -1 if this event is a capability check.
+    pub syscall_nr: i64,
+    /// Capability number (see capability(7)), or -1 if this event is a syscall entry.
+    pub capability: i32,
+}
+
+#[map]
+static EVENTS: PerfEventArray<RawAuditEvent> = PerfEventArray::new(0);
+
+/// Cgroup ID to filter events to, set by the host before attaching. 0 means "audit
+/// every process" (only used for local testing - production callers always scope
+/// this to the sandbox's own cgroup).
+#[map]
+static TARGET_CGROUP_ID: aya_ebpf::maps::Array<u64> = aya_ebpf::maps::Array::with_max_entries(1, 0);
+
+fn target_cgroup_id() -> u64 {
+    TARGET_CGROUP_ID.get(0).copied().unwrap_or(0)
+}
+
+fn should_audit(cgroup_id: u64) -> bool {
+    let target = target_cgroup_id();
+    target == 0 || target == cgroup_id
+}
+
+#[tracepoint]
+pub fn sys_enter_audit(ctx: TracePointContext) -> u32 {
+    match try_sys_enter_audit(ctx) {
+        Ok(ret) => ret,
+        Err(_) => 0,
+    }
+}
+
+fn try_sys_enter_audit(ctx: TracePointContext) -> Result<u32, c_long> {
+    let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
+    if !should_audit(cgroup_id) {
+        return Ok(0);
+    }
+
+    // The `raw_syscalls:sys_enter` tracepoint's format is `{id: u64, args: [u64; 6]}`
+    // after the common tracepoint header.
+    let syscall_nr: i64 = unsafe { ctx.read_at(8).unwrap_or(-1) };
+    let pid = (bpf_get_current_pid_tgid() >> 32) as u32;
+
+    EVENTS.output(
+        &ctx,
+        &RawAuditEvent {
+            pid,
+            cgroup_id,
+            syscall_nr,
+            capability: -1,
+        },
+        0,
+    );
+
+    Ok(0)
+}
+
+#[kprobe]
+pub fn cap_capable_audit(ctx: ProbeContext) -> u32 {
+    match try_cap_capable_audit(ctx) {
+        Ok(ret) => ret,
+        Err(_) => 0,
+    }
+}
+
+fn try_cap_capable_audit(ctx: ProbeContext) -> Result<u32, c_long> {
+    let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
+    if !should_audit(cgroup_id) {
+        return Ok(0);
+    }
+
+    // `cap_capable(const struct cred *cred, struct user_namespace *ns, int cap, ...)`
+    let capability: i32 = ctx.arg(2).unwrap_or(-1);
+    let pid = (bpf_get_current_pid_tgid() >> 32) as u32;
+
+    EVENTS.output(
+        &ctx,
+        &RawAuditEvent {
+            pid,
+            cgroup_id,
+            syscall_nr: -1,
+            capability,
+        },
+        0,
+    );
+
+    Ok(0)
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
@@ -0,0 +1,250 @@
+//! eBPF-based syscall and capability-check auditing ("observe" mode) for sandboxing.
+//!
+//! Unlike `sandbox-seccomp`, which can block syscalls, this module only observes them:
+//! every syscall entry and every `cap_capable()` check the sandboxed workload makes is
+//! recorded into a structured audit report. This is meant to be run once to profile a
+//! workload before writing a tight seccomp policy, or to assert in tests on the exact
+//! syscall/capability set a process exercised.
+
+#![cfg(target_os = "linux")]
+
+pub mod error;
+
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use aya::maps::{Array, AsyncPerfEventArray};
+use aya::programs::{KProbe, TracePoint};
+use aya::util::online_cpus;
+use aya::Ebpf;
+use bytes::BytesMut;
+use tokio::task::JoinHandle;
+use tracing::{debug, info};
+
+pub type Result<T> = std::result::Result<T, error::Error>;
+
+/// Configuration for the audit manager
+#[derive(Debug, Clone, Default)]
+pub struct AuditConfig {
+    /// Cgroup ID to scope auditing to. `0` audits every process on the host, which is
+    /// only useful for local testing - production callers should always pass the
+    /// sandbox's own cgroup ID so the report only reflects the sandboxed workload.
+    pub target_cgroup_id: u64,
+    /// Where to write the JSON audit report once `stop()` is called
+    pub output_path: Option<PathBuf>,
+}
+
+/// A single audited event
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// A syscall was entered, identified by its architecture-specific number
+    Syscall { pid: u32, nr: i64 },
+    /// A `cap_capable()` check was performed for the given capability (see capability(7))
+    Capability { pid: u32, cap: i32 },
+}
+
+/// Summary of everything observed during an audit run, suitable for diffing against
+/// what a seccomp policy allows.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct AuditReport {
+    /// Every distinct syscall number that was entered
+    pub syscalls: BTreeSet<i64>,
+    /// Every distinct capability that was checked
+    pub capabilities: BTreeSet<i32>,
+    /// The raw, arrival-ordered event stream
+    pub events: Vec<AuditEvent>,
+}
+
+impl AuditReport {
+    fn record(&mut self, event: AuditEvent) {
+        match &event {
+            AuditEvent::Syscall { nr, .. } => {
+                self.syscalls.insert(*nr);
+            }
+            AuditEvent::Capability { cap, .. } => {
+                self.capabilities.insert(*cap);
+            }
+        }
+        self.events.push(event);
+    }
+}
+
+/// Wire format shared with the `sandbox-audit-ebpf` guest programs - keep in sync.
+#[repr(C)]
+struct RawAuditEvent {
+    pid: u32,
+    cgroup_id: u64,
+    syscall_nr: i64,
+    capability: i32,
+}
+
+/// Loads and drives the `sandbox-audit-ebpf` programs, collecting a structured report
+/// of every syscall and capability check the sandboxed workload performed.
+pub struct AuditManager {
+    config: AuditConfig,
+    ebpf: Option<Ebpf>,
+    report: Arc<Mutex<AuditReport>>,
+    reader_tasks: Vec<JoinHandle<()>>,
+}
+
+impl AuditManager {
+    /// Create a new audit manager with the given configuration
+    pub fn new(config: AuditConfig) -> Self {
+        Self {
+            config,
+            ebpf: None,
+            report: Arc::new(Mutex::new(AuditReport::default())),
+            reader_tasks: Vec::new(),
+        }
+    }
+
+    /// Attach the audit eBPF programs to `raw_syscalls:sys_enter` and `cap_capable`,
+    /// scoped to `config.target_cgroup_id`, and begin collecting events in the
+    /// background.
+    ///
+    /// Returns `Err` when `CAP_BPF`/BTF support is unavailable - callers should treat
+    /// this exactly like a cgroup or seccomp setup failure: log it and continue
+    /// without auditing, rather than aborting the sandbox.
+    pub async fn start(&mut self) -> Result<()> {
+        info!("Loading eBPF audit programs (raw_syscalls:sys_enter, cap_capable)");
+
+        let mut ebpf = Ebpf::load(aya::include_bytes_aligned!(concat!(
+            env!("OUT_DIR"),
+            "/sandbox-audit-ebpf"
+        )))
+        .map_err(|e| error::Error::Load(format!("Failed to load eBPF object: {}", e)))?;
+
+        if let Some(map) = ebpf.map_mut("TARGET_CGROUP_ID") {
+            let mut target_cgroup_id: Array<_, u64> = Array::try_from(map)
+                .map_err(|e| error::Error::Load(format!("Bad TARGET_CGROUP_ID map: {}", e)))?;
+            target_cgroup_id
+                .set(0, self.config.target_cgroup_id, 0)
+                .map_err(|e| error::Error::Load(format!("Failed to set target cgroup: {}", e)))?;
+        }
+
+        let sys_enter: &mut TracePoint = ebpf
+            .program_mut("sys_enter_audit")
+            .ok_or_else(|| error::Error::Load("sys_enter_audit program missing".to_string()))?
+            .try_into()
+            .map_err(|e| error::Error::Load(format!("Bad sys_enter_audit program: {}", e)))?;
+        sys_enter.load().map_err(|e| {
+            error::Error::Unprivileged(format!("Failed to load sys_enter_audit: {}", e))
+        })?;
+        sys_enter
+            .attach("raw_syscalls", "sys_enter")
+            .map_err(|e| error::Error::Attach(format!("Failed to attach sys_enter_audit: {}", e)))?;
+
+        let cap_capable: &mut KProbe = ebpf
+            .program_mut("cap_capable_audit")
+            .ok_or_else(|| error::Error::Load("cap_capable_audit program missing".to_string()))?
+            .try_into()
+            .map_err(|e| error::Error::Load(format!("Bad cap_capable_audit program: {}", e)))?;
+        cap_capable.load().map_err(|e| {
+            error::Error::Unprivileged(format!("Failed to load cap_capable_audit: {}", e))
+        })?;
+        cap_capable
+            .attach("cap_capable", 0)
+            .map_err(|e| error::Error::Attach(format!("Failed to attach cap_capable_audit: {}", e)))?;
+
+        self.spawn_event_readers(&mut ebpf)?;
+        self.ebpf = Some(ebpf);
+
+        info!("eBPF audit programs attached");
+        Ok(())
+    }
+
+    /// Spawn one background reader per CPU, draining the `EVENTS` perf buffer into
+    /// `self.report`.
+    fn spawn_event_readers(&mut self, ebpf: &mut Ebpf) -> Result<()> {
+        let events_map = ebpf
+            .take_map("EVENTS")
+            .ok_or_else(|| error::Error::Load("EVENTS map missing".to_string()))?;
+        let mut events: AsyncPerfEventArray<_> = AsyncPerfEventArray::try_from(events_map)
+            .map_err(|e| error::Error::Load(format!("Bad EVENTS map: {}", e)))?;
+
+        for cpu_id in online_cpus()
+            .map_err(|e| error::Error::Load(format!("Failed to list online CPUs: {:?}", e)))?
+        {
+            let mut buf = events.open(cpu_id, None).map_err(|e| {
+                error::Error::Attach(format!("Failed to open perf buffer for CPU {}: {}", cpu_id, e))
+            })?;
+            let report = self.report.clone();
+
+            self.reader_tasks.push(tokio::spawn(async move {
+                let mut buffers = (0..10)
+                    .map(|_| BytesMut::with_capacity(std::mem::size_of::<RawAuditEvent>()))
+                    .collect::<Vec<_>>();
+
+                loop {
+                    let read = match buf.read_events(&mut buffers).await {
+                        Ok(read) => read,
+                        Err(e) => {
+                            debug!("Audit perf buffer reader for CPU {} stopped: {}", cpu_id, e);
+                            return;
+                        }
+                    };
+
+                    for buffer in buffers.iter().take(read.read) {
+                        if buffer.len() < std::mem::size_of::<RawAuditEvent>() {
+                            continue;
+                        }
+                        let raw = unsafe { &*(buffer.as_ptr() as *const RawAuditEvent) };
+                        let event = if raw.syscall_nr >= 0 {
+                            AuditEvent::Syscall { pid: raw.pid, nr: raw.syscall_nr }
+                        } else {
+                            AuditEvent::Capability { pid: raw.pid, cap: raw.capability }
+                        };
+                        report.lock().unwrap().record(event);
+                    }
+                }
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Stop collecting events and return the accumulated report, writing it to
+    /// `config.output_path` if one was configured.
+    pub fn stop(&mut self) -> Result<AuditReport> {
+        for task in self.reader_tasks.drain(..) {
+            task.abort();
+        }
+        self.ebpf = None;
+
+        let report = self.report.lock().unwrap().clone();
+
+        if let Some(output_path) = &self.config.output_path {
+            let json = serde_json::to_string_pretty(&report)?;
+            std::fs::write(output_path, json)?;
+            debug!("Wrote audit report to {}", output_path.display());
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_config_defaults() {
+        let config = AuditConfig::default();
+        assert_eq!(config.target_cgroup_id, 0);
+        assert!(config.output_path.is_none());
+    }
+
+    #[test]
+    fn test_audit_report_records_and_dedupes_sets() {
+        let mut report = AuditReport::default();
+        report.record(AuditEvent::Syscall { pid: 1, nr: 0 });
+        report.record(AuditEvent::Syscall { pid: 1, nr: 0 });
+        report.record(AuditEvent::Capability { pid: 1, cap: 21 });
+
+        assert_eq!(report.syscalls.len(), 1);
+        assert_eq!(report.capabilities.len(), 1);
+        assert_eq!(report.events.len(), 3);
+    }
+}
@@ -24,6 +24,14 @@ pub enum Error {
     #[error("Cgroup error: {0}")]
     Cgroup(#[from] sandbox_cgroups::error::Error),
 
+    #[cfg(feature = "audit")]
+    #[error("Audit error: {0}")]
+    Audit(#[from] sandbox_audit::error::Error),
+
+    #[cfg(feature = "resource-trace")]
+    #[error("Resource-trace error: {0}")]
+    ResourceTrace(#[from] sandbox_resource_trace::error::Error),
+
     #[error("Filesystem error: {0}")]
     Filesystem(#[from] sandbox_fs::error::Error),
 }
@@ -29,6 +29,16 @@ pub use sandbox_devices::{
     DeviceConfig, DeviceManager,
 };
 
+#[cfg(feature = "audit")]
+pub use sandbox_audit::{
+    AuditConfig, AuditEvent, AuditManager, AuditReport,
+};
+
+#[cfg(feature = "resource-trace")]
+pub use sandbox_resource_trace::{
+    ResourceEvent, ResourceTraceConfig, ResourceTraceManager, ResourceTraceReport,
+};
+
 use tracing::{debug, info};
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -55,6 +65,14 @@ pub struct Sandbox {
     device_config: Option<sandbox_devices::DeviceConfig>,
     #[cfg(feature = "devices")]
     device_manager: Option<sandbox_devices::DeviceManager>,
+    #[cfg(feature = "audit")]
+    audit_config: Option<sandbox_audit::AuditConfig>,
+    #[cfg(feature = "audit")]
+    audit_manager: Option<sandbox_audit::AuditManager>,
+    #[cfg(feature = "resource-trace")]
+    resource_trace_config: Option<sandbox_resource_trace::ResourceTraceConfig>,
+    #[cfg(feature = "resource-trace")]
+    resource_trace_manager: Option<sandbox_resource_trace::ResourceTraceManager>,
 }
 
 impl Default for Sandbox {
@@ -106,6 +124,14 @@ impl Sandbox {
             device_config: None,
             #[cfg(feature = "devices")]
             device_manager: None,
+            #[cfg(feature = "audit")]
+            audit_config: None,
+            #[cfg(feature = "audit")]
+            audit_manager: None,
+            #[cfg(feature = "resource-trace")]
+            resource_trace_config: None,
+            #[cfg(feature = "resource-trace")]
+            resource_trace_manager: None,
         }
     }
 
@@ -135,6 +161,14 @@ impl Sandbox {
             device_config: None,
             #[cfg(feature = "devices")]
             device_manager: None,
+            #[cfg(feature = "audit")]
+            audit_config: None,
+            #[cfg(feature = "audit")]
+            audit_manager: None,
+            #[cfg(feature = "resource-trace")]
+            resource_trace_config: None,
+            #[cfg(feature = "resource-trace")]
+            resource_trace_manager: None,
         }
     }
 
@@ -231,6 +265,41 @@ impl Sandbox {
         self
     }
 
+    /// Enable eBPF syscall/capability auditing ("observe" mode) for this sandbox
+    #[cfg(feature = "audit")]
+    pub fn with_audit(mut self, config: sandbox_audit::AuditConfig) -> Self {
+        self.audit_config = Some(config.clone());
+        self.audit_manager = Some(sandbox_audit::AuditManager::new(config));
+        self
+    }
+
+    /// Enable eBPF syscall/capability auditing with default configuration
+    #[cfg(feature = "audit")]
+    pub fn with_default_audit(mut self) -> Self {
+        let config = sandbox_audit::AuditConfig::default();
+        self.audit_config = Some(config.clone());
+        self.audit_manager = Some(sandbox_audit::AuditManager::new(config));
+        self
+    }
+
+    /// Enable eBPF resource-event tracing (fork/OOM-kill/CFS-throttle counts) for
+    /// this sandbox
+    #[cfg(feature = "resource-trace")]
+    pub fn with_resource_trace(mut self, config: sandbox_resource_trace::ResourceTraceConfig) -> Self {
+        self.resource_trace_config = Some(config.clone());
+        self.resource_trace_manager = Some(sandbox_resource_trace::ResourceTraceManager::new(config));
+        self
+    }
+
+    /// Enable eBPF resource-event tracing with default configuration
+    #[cfg(feature = "resource-trace")]
+    pub fn with_default_resource_trace(mut self) -> Self {
+        let config = sandbox_resource_trace::ResourceTraceConfig::default();
+        self.resource_trace_config = Some(config.clone());
+        self.resource_trace_manager = Some(sandbox_resource_trace::ResourceTraceManager::new(config));
+        self
+    }
+
     /// Set the target PID for network operations (required for internet access)
     #[cfg(feature = "net")]
     pub fn set_network_target_pid(&mut self, pid: u32) -> Result<()> {
@@ -321,6 +390,34 @@ impl Sandbox {
             }
         }
 
+        // Attach audit eBPF programs if enabled
+        #[cfg(feature = "audit")]
+        if let Some(ref mut audit_manager) = self.audit_manager {
+            match audit_manager.start().await {
+                Ok(()) => {
+                    debug!("Sandbox eBPF audit programs attached successfully");
+                }
+                Err(e) => {
+                    // Requires CAP_BPF/BTF kernel support - absent in many test environments
+                    debug!("Audit eBPF setup failed (expected in some environments): {}", e);
+                }
+            }
+        }
+
+        // Attach resource-trace eBPF programs if enabled
+        #[cfg(feature = "resource-trace")]
+        if let Some(ref mut resource_trace_manager) = self.resource_trace_manager {
+            match resource_trace_manager.start().await {
+                Ok(()) => {
+                    debug!("Sandbox eBPF resource-trace programs attached successfully");
+                }
+                Err(e) => {
+                    // Requires CAP_BPF/BTF kernel support - absent in many test environments
+                    debug!("Resource-trace eBPF setup failed (expected in some environments): {}", e);
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -356,6 +453,22 @@ impl Sandbox {
             }
         }
 
+        // Stop audit collection
+        #[cfg(feature = "audit")]
+        if let Some(ref mut audit_manager) = self.audit_manager {
+            if let Err(e) = audit_manager.stop() {
+                debug!("Audit eBPF teardown failed: {}", e);
+            }
+        }
+
+        // Stop resource-trace collection
+        #[cfg(feature = "resource-trace")]
+        if let Some(ref mut resource_trace_manager) = self.resource_trace_manager {
+            if let Err(e) = resource_trace_manager.stop() {
+                debug!("Resource-trace eBPF teardown failed: {}", e);
+            }
+        }
+
         Ok(())
     }
 
@@ -378,6 +491,57 @@ impl Sandbox {
         }
     }
 
+    /// Stop audit collection and return the accumulated report (if auditing is enabled)
+    #[cfg(feature = "audit")]
+    pub fn collect_audit_report(&mut self) -> Result<Option<sandbox_audit::AuditReport>> {
+        if let Some(ref mut audit_manager) = self.audit_manager {
+            Ok(Some(audit_manager.stop()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Get the filesystem path of this sandbox's cgroup (if cgroups are enabled and
+    /// `setup_limits()` has run)
+    #[cfg(feature = "cgroups")]
+    pub fn cgroup_path(&self) -> Option<&std::path::Path> {
+        self.cgroup_manager.as_ref().and_then(|m| m.cgroup_path())
+    }
+
+    /// Set the target cgroup for audit eBPF programs to scope their observations to
+    /// (if auditing is enabled)
+    #[cfg(feature = "audit")]
+    pub fn set_audit_target_cgroup(&mut self, cgroup_id: u64) {
+        if let Some(ref mut config) = self.audit_config {
+            config.target_cgroup_id = cgroup_id;
+            self.audit_manager = Some(sandbox_audit::AuditManager::new(config.clone()));
+        }
+    }
+
+    /// Stop resource-trace collection and return the accumulated report (if
+    /// resource tracing is enabled)
+    #[cfg(feature = "resource-trace")]
+    pub fn collect_resource_trace_report(
+        &mut self,
+    ) -> Result<Option<sandbox_resource_trace::ResourceTraceReport>> {
+        if let Some(ref mut resource_trace_manager) = self.resource_trace_manager {
+            Ok(Some(resource_trace_manager.stop()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Set the target cgroup for resource-trace eBPF programs to scope their
+    /// observations to (if resource tracing is enabled)
+    #[cfg(feature = "resource-trace")]
+    pub fn set_resource_trace_target_cgroup(&mut self, cgroup_id: u64) {
+        if let Some(ref mut config) = self.resource_trace_config {
+            config.target_cgroup_id = cgroup_id;
+            self.resource_trace_manager =
+                Some(sandbox_resource_trace::ResourceTraceManager::new(config.clone()));
+        }
+    }
+
     /// Get the current namespace configuration
     pub fn namespace_config(&self) -> &namespaces::NamespaceConfig {
         &self.namespace_config
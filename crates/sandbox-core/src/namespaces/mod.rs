@@ -1,7 +1,7 @@
 //! Linux namespace management for sandbox isolation.
 
 use nix::sched::{unshare, CloneFlags};
-use nix::unistd::{getuid, setgroups, setresgid, setresuid, Uid, Gid};
+use nix::unistd::{getuid, setresgid, setresuid, Uid, Gid};
 use tracing::{debug, info, warn};
 
 use crate::error::Error;
@@ -46,22 +46,30 @@ impl NamespaceManager {
         Self { config }
     }
 
-    /// Enter all configured namespaces in a single unshare() call
+    /// Enter all configured namespaces
     ///
-    /// This creates ALL namespaces (user, mount, PID, UTS, IPC) in one atomic operation.
     /// User namespaces (CLONE_NEWUSER) enable unprivileged creation since Linux 3.8.
     /// When CLONE_NEWUSER is included, the process becomes "root" within the user namespace,
-    /// allowing creation of other namespaces without CAP_SYS_ADMIN in the parent namespace.
+    /// allowing creation of other namespaces without CAP_SYS_ADMIN in the parent namespace -
+    /// but only *after* a uid/gid mapping has been written, since until then the process is
+    /// mapped to the overflow uid/gid and has no capabilities at all.
     ///
-    /// After namespace creation, UID/GID mappings are set up if user namespaces are enabled.
+    /// Because of that ordering requirement, CLONE_NEWUSER is unshared on its own first so the
+    /// mapping can be established, and the remaining namespaces (mount, PID, UTS, IPC) are
+    /// unshared in a second call once the process is root in its own user namespace.
     pub fn enter_namespaces(&self) -> Result<()> {
         info!("Entering namespaces: {:?}", self.config);
 
-        let mut flags = CloneFlags::empty();
-
         if self.config.user_ns {
-            flags |= CloneFlags::CLONE_NEWUSER;
+            unshare(CloneFlags::CLONE_NEWUSER).map_err(|e| {
+                warn!("Failed to unshare user namespace: {}", e);
+                Error::Namespace(format!("Failed to unshare user namespace: {}", e))
+            })?;
+            self.setup_user_mappings()?;
         }
+
+        let mut flags = CloneFlags::empty();
+
         if self.config.mount_ns {
             flags |= CloneFlags::CLONE_NEWNS;
         }
@@ -87,11 +95,6 @@ impl NamespaceManager {
             })?;
         }
 
-        // Set up user namespace mappings if enabled
-        if self.config.user_ns {
-            self.setup_user_mappings()?;
-        }
-
         debug!("Successfully entered namespaces");
         Ok(())
     }
@@ -106,34 +109,33 @@ impl NamespaceManager {
     /// in the parent namespace. However, if the user namespace was created by an
     /// unprivileged user, they can only map their own UID/GID (no privilege escalation).
     fn setup_user_mappings(&self) -> Result<()> {
-        // For user namespaces, we need to write to /proc/self/uid_map and /proc/self/gid_map
-        // This must be done after unshare but before executing the child
+        // Unprivileged writes to gid_map are rejected by the kernel unless
+        // /proc/self/setgroups has first been set to "deny" - this must happen before the
+        // gid_map write below, not after (the old setgroups(&[]) syscall approach is too
+        // late by the time gid_map is already writable).
+        self.write_mapping("/proc/self/setgroups", "deny")?;
 
         if let Some(uid_map) = &self.config.uid_map {
             self.write_mapping("/proc/self/uid_map", uid_map)?;
         } else {
-            // Default mapping: current UID maps to root in namespace
+            // Default mapping: give the sandboxed process root (uid 0) inside its own
+            // namespace, backed by our real uid outside of it.
             let uid = getuid().as_raw();
-            let default_uid_map = format!("{} {} 1", uid, uid);
+            let default_uid_map = format!("0 {} 1", uid);
             self.write_mapping("/proc/self/uid_map", &default_uid_map)?;
         }
 
         if let Some(gid_map) = &self.config.gid_map {
             self.write_mapping("/proc/self/gid_map", gid_map)?;
         } else {
-            // Default mapping: current GID maps to root in namespace
+            // Default mapping: give the sandboxed process root (gid 0) inside its own
+            // namespace, backed by our real gid outside of it.
             let gid = nix::unistd::getgid().as_raw();
-            let default_gid_map = format!("{} {} 1", gid, gid);
+            let default_gid_map = format!("0 {} 1", gid);
             self.write_mapping("/proc/self/gid_map", &default_gid_map)?;
         }
 
-        // Set groups to empty for user namespaces
-        setgroups(&[]).map_err(|e| {
-            warn!("Failed to set groups: {}", e);
-            Error::Namespace(format!("Failed to set groups: {}", e))
-        })?;
-
-        // Switch to root in the namespace
+        // Switch to root in the namespace, now that uid/gid 0 are mapped
         setresuid(Uid::from_raw(0), Uid::from_raw(0), Uid::from_raw(0)).map_err(|e| {
             warn!("Failed to set uid: {}", e);
             Error::Namespace(format!("Failed to set uid: {}", e))
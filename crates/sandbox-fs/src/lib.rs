@@ -34,6 +34,16 @@ pub struct FilesystemConfig {
     pub session_state_dir: Option<String>,
     /// Whether to use static mode (blacklist + overlays) vs dynamic mode
     pub static_mode: bool,
+    /// Read-only bind mounts: (source, destination). Unlike `readonly_paths`, the
+    /// destination can differ from the source, matching the `--ro-bind SRC[:DST]`
+    /// hardening container runtimes like youki apply during init.
+    pub ro_binds: Vec<(String, String)>,
+    /// Paths to mask: a plain file gets `/dev/null` bound over it, a directory gets a
+    /// fresh read-only tmpfs mounted over it (e.g. `/proc/kcore`, `/sys/firmware`).
+    pub mask_paths: Vec<String>,
+    /// Sysctl values to set inside the sandbox's own namespaces before the process
+    /// starts, as `(key, value)` pairs with dotted keys (e.g. `net.ipv4.ip_forward`).
+    pub sysctls: Vec<(String, String)>,
 }
 
 impl Default for FilesystemConfig {
@@ -61,6 +71,9 @@ impl Default for FilesystemConfig {
             ],
             session_state_dir: None,
             static_mode: false,
+            ro_binds: Vec::new(),
+            mask_paths: Vec::new(),
+            sysctls: Vec::new(),
         }
     }
 }
@@ -144,6 +157,45 @@ impl FilesystemManager {
             }
         }
 
+        // Set up read-only bind mounts
+        for (source, target) in &self.config.ro_binds {
+            match self.ro_bind(source, target) {
+                Ok(()) => debug!("Created read-only bind mount: {} -> {}", source, target),
+                Err(e) => {
+                    debug!(
+                        "Failed to create read-only bind mount (expected in test environment): {}",
+                        e
+                    );
+                }
+            }
+        }
+
+        // Mask sensitive paths
+        for mask_path in &self.config.mask_paths {
+            match self.mask_path(mask_path) {
+                Ok(()) => debug!("Masked {}", mask_path),
+                Err(e) => {
+                    debug!(
+                        "Failed to mask {} (expected in test environment): {}",
+                        mask_path, e
+                    );
+                }
+            }
+        }
+
+        // Apply sysctl hardening values
+        for (key, value) in &self.config.sysctls {
+            match self.set_sysctl(key, value) {
+                Ok(()) => debug!("Set sysctl {} = {}", key, value),
+                Err(e) => {
+                    debug!(
+                        "Failed to set sysctl {} (expected in test environment): {}",
+                        key, e
+                    );
+                }
+            }
+        }
+
         // Ensure working directory is writable
         if let Some(work_dir) = &self.config.working_dir {
             match self.ensure_writable(work_dir) {
@@ -290,6 +342,98 @@ impl FilesystemManager {
         Ok(())
     }
 
+    /// Bind-mount `source` onto `target` and remount read-only - the `--ro-bind` hardening
+    /// container runtimes like youki apply during init.
+    fn ro_bind(&self, source: &str, target: &str) -> Result<()> {
+        let source_path = Path::new(source);
+
+        if !source_path.exists() {
+            debug!("Source path {} does not exist, skipping ro-bind", source);
+            return Ok(());
+        }
+
+        if let Some(parent) = Path::new(target).parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                Error::Mount(format!(
+                    "Failed to create target directory {}: {}",
+                    parent.display(),
+                    e
+                ))
+            })?;
+        }
+
+        info!("Creating read-only bind mount: {} -> {}", source, target);
+
+        mount(Some(source), target, None::<&str>, MsFlags::MS_BIND, None::<&str>).map_err(
+            |e| {
+                warn!("Failed to bind mount {} to {}: {}", source, target, e);
+                Error::Mount(format!("Failed to bind mount {} to {}: {}", source, target, e))
+            },
+        )?;
+
+        mount(
+            Some(source),
+            target,
+            None::<&str>,
+            MsFlags::MS_BIND | MsFlags::MS_REMOUNT | MsFlags::MS_RDONLY,
+            None::<&str>,
+        )
+        .map_err(|e| {
+            warn!("Failed to remount {} as readonly: {}", target, e);
+            Error::Mount(format!("Failed to remount {} as readonly: {}", target, e))
+        })?;
+
+        debug!("Successfully ro-bind-mounted {} -> {}", source, target);
+        Ok(())
+    }
+
+    /// Mask a sensitive path: bind `/dev/null` over a file, or mount a fresh read-only
+    /// tmpfs over a directory (mirrors the masked paths runc/youki apply over things
+    /// like `/proc/kcore` and `/sys/firmware`).
+    fn mask_path(&self, path: &str) -> Result<()> {
+        let path_obj = Path::new(path);
+
+        if !path_obj.exists() {
+            debug!("Path {} does not exist, skipping mask", path);
+            return Ok(());
+        }
+
+        if path_obj.is_dir() {
+            mount(Some("tmpfs"), path, Some("tmpfs"), MsFlags::MS_RDONLY, Some("mode=0000"))
+                .map_err(|e| {
+                    warn!("Failed to mask directory {}: {}", path, e);
+                    Error::Mount(format!("Failed to mask directory {}: {}", path, e))
+                })?;
+        } else {
+            mount(Some("/dev/null"), path, None::<&str>, MsFlags::MS_BIND, None::<&str>)
+                .map_err(|e| {
+                    warn!("Failed to mask file {}: {}", path, e);
+                    Error::Mount(format!("Failed to mask file {}: {}", path, e))
+                })?;
+        }
+
+        debug!("Successfully masked {}", path);
+        Ok(())
+    }
+
+    /// Write a sysctl value under `/proc/sys` inside the sandbox's own namespaces.
+    ///
+    /// Dots in `key` are translated to path separators (e.g. `net.ipv4.ip_forward`
+    /// becomes `/proc/sys/net/ipv4/ip_forward`).
+    fn set_sysctl(&self, key: &str, value: &str) -> Result<()> {
+        let proc_path = format!("/proc/sys/{}", key.replace('.', "/"));
+
+        info!("Setting sysctl {} = {} ({})", key, value, proc_path);
+
+        std::fs::write(&proc_path, value).map_err(|e| {
+            warn!("Failed to set sysctl {} via {}: {}", key, proc_path, e);
+            Error::Mount(format!("Failed to set sysctl {} via {}: {}", key, proc_path, e))
+        })?;
+
+        debug!("Successfully set sysctl {}", key);
+        Ok(())
+    }
+
     /// Ensure a path is writable (create directory if needed)
     fn ensure_writable(&self, path: &str) -> Result<()> {
         let path_obj = Path::new(path);
@@ -469,6 +613,23 @@ mod tests {
         assert!(!config.blacklist_paths.is_empty()); // Should have default blacklisted paths
         assert!(config.session_state_dir.is_none());
         assert!(!config.static_mode);
+        assert!(config.ro_binds.is_empty());
+        assert!(config.mask_paths.is_empty());
+        assert!(config.sysctls.is_empty());
+    }
+
+    #[test]
+    fn test_mask_path_missing_is_noop() {
+        let manager = FilesystemManager::new();
+        // Masking a path that doesn't exist should be a no-op, not an error.
+        assert!(manager.mask_path("/this/path/does/not/exist").is_ok());
+    }
+
+    #[test]
+    fn test_ro_bind_missing_source_is_noop() {
+        let manager = FilesystemManager::new();
+        // ro-bind of a missing source should be a no-op, not an error.
+        assert!(manager.ro_bind("/this/path/does/not/exist", "/tmp/ro-bind-target").is_ok());
     }
 
     #[test]
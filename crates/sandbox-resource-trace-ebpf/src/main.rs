@@ -0,0 +1,122 @@
+//! eBPF programs for `sandbox-resource-trace`: count process creation, OOM kills,
+//! and CFS bandwidth throttling events for one cgroup.
+//!
+//! This crate is the `aya-ebpf` guest half of `sandbox-resource-trace` - it is
+//! compiled to BPF bytecode and loaded into the kernel by
+//! `sandbox_resource_trace::ResourceTraceManager::start()`. Like `sandbox-audit-ebpf`,
+//! it only counts events; it never makes enforcement decisions.
+
+#![no_std]
+#![no_main]
+
+use aya_ebpf::{
+    cty::c_long,
+    helpers::bpf_get_current_cgroup_id,
+    macros::{kprobe, map, tracepoint},
+    maps::PerfEventArray,
+    programs::{ProbeContext, TracePointContext},
+};
+
+/// One traced event: a fork, an OOM kill, or a CFS throttle.
+///
+/// Mirrors `sandbox_resource_trace::RawResourceEvent` - keep the two in sync, since
+/// the host side reinterprets these raw bytes from the perf buffer.
+#[repr(C)]
+pub struct RawResourceEvent {
+    pub pid: u32,
+    pub cgroup_id: u64,
+    /// 0 = fork, 1 = OOM kill, 2 = CFS throttle
+    pub kind: u8,
+}
+
+#[map]
+static EVENTS: PerfEventArray<RawResourceEvent> = PerfEventArray::new(0);
+
+/// Cgroup ID to filter events to, set by the host before attaching. 0 means "trace
+/// every process" (only used for local testing - production callers always scope
+/// this to the sandbox's own cgroup).
+#[map]
+static TARGET_CGROUP_ID: aya_ebpf::maps::Array<u64> = aya_ebpf::maps::Array::with_max_entries(1, 0);
+
+fn target_cgroup_id() -> u64 {
+    TARGET_CGROUP_ID.get(0).copied().unwrap_or(0)
+}
+
+fn should_trace(cgroup_id: u64) -> bool {
+    let target = target_cgroup_id();
+    target == 0 || target == cgroup_id
+}
+
+fn emit(ctx_cgroup_id: u64, pid: u32, kind: u8, output: impl FnOnce(&RawResourceEvent)) {
+    if !should_trace(ctx_cgroup_id) {
+        return;
+    }
+    output(&RawResourceEvent {
+        pid,
+        cgroup_id: ctx_cgroup_id,
+        kind,
+    });
+}
+
+#[tracepoint]
+pub fn trace_process_fork(ctx: TracePointContext) -> u32 {
+    match try_trace_process_fork(ctx) {
+        Ok(ret) => ret,
+        Err(_) => 0,
+    }
+}
+
+fn try_trace_process_fork(ctx: TracePointContext) -> Result<u32, c_long> {
+    let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
+    // `sched:sched_process_fork` format is `{parent_comm[16], parent_pid,
+    // child_comm[16], child_pid}` after the 8-byte common tracepoint header, so
+    // `child_pid` (the newly created task) sits at offset 8 + 16 + 4 + 16 = 44.
+    let child_pid: u32 = unsafe { ctx.read_at(44).unwrap_or(0) };
+    emit(cgroup_id, child_pid, 0, |event| {
+        EVENTS.output(&ctx, event, 0);
+    });
+    Ok(0)
+}
+
+#[kprobe]
+pub fn trace_oom_kill(ctx: ProbeContext) -> u32 {
+    match try_trace_oom_kill(ctx) {
+        Ok(ret) => ret,
+        Err(_) => 0,
+    }
+}
+
+fn try_trace_oom_kill(ctx: ProbeContext) -> Result<u32, c_long> {
+    let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
+    // `oom_kill_process(struct oom_control *oc, const char *message)` doesn't carry
+    // the killed PID directly in its arguments; the PID recorded is that of the
+    // task current when the kprobe fires, i.e. the task whose allocation hit the
+    // OOM condition, which is not necessarily the victim.
+    let pid = (aya_ebpf::helpers::bpf_get_current_pid_tgid() >> 32) as u32;
+    emit(cgroup_id, pid, 1, |event| {
+        EVENTS.output(&ctx, event, 0);
+    });
+    Ok(0)
+}
+
+#[kprobe]
+pub fn trace_cfs_throttle(ctx: ProbeContext) -> u32 {
+    match try_trace_cfs_throttle(ctx) {
+        Ok(ret) => ret,
+        Err(_) => 0,
+    }
+}
+
+fn try_trace_cfs_throttle(ctx: ProbeContext) -> Result<u32, c_long> {
+    let cgroup_id = unsafe { bpf_get_current_cgroup_id() };
+    let pid = (aya_ebpf::helpers::bpf_get_current_pid_tgid() >> 32) as u32;
+    emit(cgroup_id, pid, 2, |event| {
+        EVENTS.output(&ctx, event, 0);
+    });
+    Ok(0)
+}
+
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
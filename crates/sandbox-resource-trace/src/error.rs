@@ -0,0 +1,22 @@
+//! Error types for eBPF resource-event tracing.
+
+use thiserror::Error;
+
+/// Errors that can occur while loading or running the resource-trace eBPF programs
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    #[error("Failed to load eBPF program: {0}")]
+    Load(String),
+
+    #[error("Failed to attach eBPF program: {0}")]
+    Attach(String),
+
+    #[error("Insufficient privileges for eBPF tracing (requires CAP_BPF): {0}")]
+    Unprivileged(String),
+}
@@ -0,0 +1,270 @@
+//! eBPF-based kernel-level resource-event tracing for sandboxed workloads.
+//!
+//! Sibling to `sandbox-audit`: where that crate observes syscalls and capability
+//! checks, this one counts the specific kernel events that `pids`/`memory`/`cpu`
+//! cgroup enforcement produces - process creation, OOM kills, and CFS bandwidth
+//! throttling - scoped to a single cgroup. It exists so a test harness (see
+//! `tests/cgroup-enforcement`) can assert that a fork-bomb/memory-hog/cpu-burner
+//! test was actually stopped by the kernel, rather than inferring it from a
+//! process timeout or exit code.
+
+#![cfg(target_os = "linux")]
+
+pub mod error;
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use aya::maps::{Array, AsyncPerfEventArray};
+use aya::programs::{KProbe, TracePoint};
+use aya::util::online_cpus;
+use aya::Ebpf;
+use bytes::BytesMut;
+use tokio::task::JoinHandle;
+use tracing::{debug, info};
+
+pub type Result<T> = std::result::Result<T, error::Error>;
+
+/// Configuration for the resource-trace manager
+#[derive(Debug, Clone, Default)]
+pub struct ResourceTraceConfig {
+    /// Cgroup ID to scope tracing to. `0` traces every process on the host, which
+    /// is only useful for local testing - production callers should always pass
+    /// the sandbox's own cgroup ID so counts only reflect the sandboxed workload.
+    pub target_cgroup_id: u64,
+    /// Where to write the JSON trace report once `stop()` is called
+    pub output_path: Option<PathBuf>,
+}
+
+/// A single traced kernel event
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResourceEvent {
+    /// `sched_process_fork` fired for a traced cgroup - a `clone`/`fork` was made
+    Forked { pid: u32 },
+    /// `oom_kill_process` fired for a traced cgroup - `pid` is the task that was
+    /// running when it fired (the one that hit the OOM), not necessarily the victim
+    OomKilled { pid: u32 },
+    /// `throttle_cfs_rq` fired for a traced cgroup - `pid`'s run queue was
+    /// throttled for exceeding its CFS bandwidth quota
+    Throttled { pid: u32 },
+}
+
+/// Summary of everything observed during a trace run, suitable for asserting
+/// kernel-level enforcement happened instead of inferring it from a timeout.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ResourceTraceReport {
+    pub fork_count: u64,
+    pub oom_kill_count: u64,
+    pub throttle_count: u64,
+    /// The raw, arrival-ordered event stream
+    pub events: Vec<ResourceEvent>,
+}
+
+impl ResourceTraceReport {
+    fn record(&mut self, event: ResourceEvent) {
+        match &event {
+            ResourceEvent::Forked { .. } => self.fork_count += 1,
+            ResourceEvent::OomKilled { .. } => self.oom_kill_count += 1,
+            ResourceEvent::Throttled { .. } => self.throttle_count += 1,
+        }
+        self.events.push(event);
+    }
+}
+
+/// Wire format shared with the `sandbox-resource-trace-ebpf` guest program - keep
+/// in sync.
+#[repr(C)]
+struct RawResourceEvent {
+    pid: u32,
+    cgroup_id: u64,
+    /// 0 = fork, 1 = OOM kill, 2 = CFS throttle
+    kind: u8,
+}
+
+/// Loads and drives the `sandbox-resource-trace-ebpf` programs, collecting a
+/// structured count of fork/OOM-kill/throttle events for one cgroup.
+///
+/// The guest object is compiled CO-RE (Compile Once - Run Everywhere): its field
+/// offsets are relocated against the running kernel's BTF (`/sys/kernel/btf/vmlinux`)
+/// at load time by `aya`, so the same precompiled object attaches correctly
+/// regardless of the target kernel's exact struct layouts.
+pub struct ResourceTraceManager {
+    config: ResourceTraceConfig,
+    ebpf: Option<Ebpf>,
+    report: Arc<Mutex<ResourceTraceReport>>,
+    reader_tasks: Vec<JoinHandle<()>>,
+}
+
+impl ResourceTraceManager {
+    /// Create a new resource-trace manager with the given configuration
+    pub fn new(config: ResourceTraceConfig) -> Self {
+        Self {
+            config,
+            ebpf: None,
+            report: Arc::new(Mutex::new(ResourceTraceReport::default())),
+            reader_tasks: Vec::new(),
+        }
+    }
+
+    /// Attach the tracing eBPF programs to `sched:sched_process_fork`,
+    /// `oom_kill_process`, and `throttle_cfs_rq`, scoped to
+    /// `config.target_cgroup_id`, and begin collecting events in the background.
+    ///
+    /// Returns `Err` when `CAP_BPF`/BTF support is unavailable - callers should
+    /// treat this exactly like a cgroup or seccomp setup failure: log it and fall
+    /// back to whatever non-kernel verification is available, rather than
+    /// aborting the test.
+    pub async fn start(&mut self) -> Result<()> {
+        info!("Loading eBPF resource-trace programs (sched_process_fork, oom_kill_process, throttle_cfs_rq)");
+
+        let mut ebpf = Ebpf::load(aya::include_bytes_aligned!(concat!(
+            env!("OUT_DIR"),
+            "/sandbox-resource-trace-ebpf"
+        )))
+        .map_err(|e| error::Error::Load(format!("Failed to load eBPF object: {}", e)))?;
+
+        if let Some(map) = ebpf.map_mut("TARGET_CGROUP_ID") {
+            let mut target_cgroup_id: Array<_, u64> = Array::try_from(map)
+                .map_err(|e| error::Error::Load(format!("Bad TARGET_CGROUP_ID map: {}", e)))?;
+            target_cgroup_id
+                .set(0, self.config.target_cgroup_id, 0)
+                .map_err(|e| error::Error::Load(format!("Failed to set target cgroup: {}", e)))?;
+        }
+
+        let fork: &mut TracePoint = ebpf
+            .program_mut("trace_process_fork")
+            .ok_or_else(|| error::Error::Load("trace_process_fork program missing".to_string()))?
+            .try_into()
+            .map_err(|e| error::Error::Load(format!("Bad trace_process_fork program: {}", e)))?;
+        fork.load()
+            .map_err(|e| error::Error::Unprivileged(format!("Failed to load trace_process_fork: {}", e)))?;
+        fork.attach("sched", "sched_process_fork")
+            .map_err(|e| error::Error::Attach(format!("Failed to attach trace_process_fork: {}", e)))?;
+
+        let oom_kill: &mut KProbe = ebpf
+            .program_mut("trace_oom_kill")
+            .ok_or_else(|| error::Error::Load("trace_oom_kill program missing".to_string()))?
+            .try_into()
+            .map_err(|e| error::Error::Load(format!("Bad trace_oom_kill program: {}", e)))?;
+        oom_kill
+            .load()
+            .map_err(|e| error::Error::Unprivileged(format!("Failed to load trace_oom_kill: {}", e)))?;
+        oom_kill
+            .attach("oom_kill_process", 0)
+            .map_err(|e| error::Error::Attach(format!("Failed to attach trace_oom_kill: {}", e)))?;
+
+        let throttle: &mut KProbe = ebpf
+            .program_mut("trace_cfs_throttle")
+            .ok_or_else(|| error::Error::Load("trace_cfs_throttle program missing".to_string()))?
+            .try_into()
+            .map_err(|e| error::Error::Load(format!("Bad trace_cfs_throttle program: {}", e)))?;
+        throttle
+            .load()
+            .map_err(|e| error::Error::Unprivileged(format!("Failed to load trace_cfs_throttle: {}", e)))?;
+        throttle
+            .attach("throttle_cfs_rq", 0)
+            .map_err(|e| error::Error::Attach(format!("Failed to attach trace_cfs_throttle: {}", e)))?;
+
+        self.spawn_event_readers(&mut ebpf)?;
+        self.ebpf = Some(ebpf);
+
+        info!("eBPF resource-trace programs attached");
+        Ok(())
+    }
+
+    /// Spawn one background reader per CPU, draining the `EVENTS` perf buffer into
+    /// `self.report`.
+    fn spawn_event_readers(&mut self, ebpf: &mut Ebpf) -> Result<()> {
+        let events_map = ebpf
+            .take_map("EVENTS")
+            .ok_or_else(|| error::Error::Load("EVENTS map missing".to_string()))?;
+        let mut events: AsyncPerfEventArray<_> = AsyncPerfEventArray::try_from(events_map)
+            .map_err(|e| error::Error::Load(format!("Bad EVENTS map: {}", e)))?;
+
+        for cpu_id in online_cpus()
+            .map_err(|e| error::Error::Load(format!("Failed to list online CPUs: {:?}", e)))?
+        {
+            let mut buf = events.open(cpu_id, None).map_err(|e| {
+                error::Error::Attach(format!("Failed to open perf buffer for CPU {}: {}", cpu_id, e))
+            })?;
+            let report = self.report.clone();
+
+            self.reader_tasks.push(tokio::spawn(async move {
+                let mut buffers = (0..10)
+                    .map(|_| BytesMut::with_capacity(std::mem::size_of::<RawResourceEvent>()))
+                    .collect::<Vec<_>>();
+
+                loop {
+                    let read = match buf.read_events(&mut buffers).await {
+                        Ok(read) => read,
+                        Err(e) => {
+                            debug!("Resource-trace perf buffer reader for CPU {} stopped: {}", cpu_id, e);
+                            return;
+                        }
+                    };
+
+                    for buffer in buffers.iter().take(read.read) {
+                        if buffer.len() < std::mem::size_of::<RawResourceEvent>() {
+                            continue;
+                        }
+                        let raw = unsafe { &*(buffer.as_ptr() as *const RawResourceEvent) };
+                        let event = match raw.kind {
+                            0 => ResourceEvent::Forked { pid: raw.pid },
+                            1 => ResourceEvent::OomKilled { pid: raw.pid },
+                            _ => ResourceEvent::Throttled { pid: raw.pid },
+                        };
+                        report.lock().unwrap().record(event);
+                    }
+                }
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Stop collecting events and return the accumulated report, writing it to
+    /// `config.output_path` if one was configured.
+    pub fn stop(&mut self) -> Result<ResourceTraceReport> {
+        for task in self.reader_tasks.drain(..) {
+            task.abort();
+        }
+        self.ebpf = None;
+
+        let report = self.report.lock().unwrap().clone();
+
+        if let Some(output_path) = &self.config.output_path {
+            let json = serde_json::to_string_pretty(&report)?;
+            std::fs::write(output_path, json)?;
+            debug!("Wrote resource-trace report to {}", output_path.display());
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_trace_config_defaults() {
+        let config = ResourceTraceConfig::default();
+        assert_eq!(config.target_cgroup_id, 0);
+        assert!(config.output_path.is_none());
+    }
+
+    #[test]
+    fn test_resource_trace_report_counts_by_kind() {
+        let mut report = ResourceTraceReport::default();
+        report.record(ResourceEvent::Forked { pid: 1 });
+        report.record(ResourceEvent::Forked { pid: 2 });
+        report.record(ResourceEvent::OomKilled { pid: 1 });
+        report.record(ResourceEvent::Throttled { pid: 1 });
+
+        assert_eq!(report.fork_count, 2);
+        assert_eq!(report.oom_kill_count, 1);
+        assert_eq!(report.throttle_count, 1);
+        assert_eq!(report.events.len(), 4);
+    }
+}
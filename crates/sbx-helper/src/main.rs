@@ -30,6 +30,15 @@ struct Args {
     #[arg(long)]
     no_user_ns: bool,
 
+    /// Force user namespace isolation even when running as root
+    ///
+    /// This is auto-enabled when not running as root, since unprivileged callers need a
+    /// user namespace (with a root-in-namespace uid/gid mapping) before they can create
+    /// mount/PID/etc. namespaces at all. Root callers already have CAP_SYS_ADMIN and don't
+    /// need this, but can opt in for testing with `--userns`.
+    #[arg(long)]
+    userns: bool,
+
     /// Disable mount namespace isolation
     ///
     /// Note: Mount operations require CAP_SYS_ADMIN (typically root privileges).
@@ -59,6 +68,21 @@ struct Args {
     #[arg(long)]
     blacklist: Vec<String>,
 
+    /// Bind-mount SRC read-only, optionally onto a different DST (SRC[:DST], can be
+    /// specified multiple times)
+    #[arg(long = "ro-bind")]
+    ro_bind: Vec<String>,
+
+    /// Mask PATH: bind /dev/null over a file, or a read-only tmpfs over a directory
+    /// (can be specified multiple times)
+    #[arg(long = "mask-path")]
+    mask_path: Vec<String>,
+
+    /// Set a sysctl value inside the sandbox as KEY=VALUE (e.g.
+    /// `net.ipv4.ip_forward=0`, can be specified multiple times)
+    #[arg(long)]
+    sysctl: Vec<String>,
+
     /// Enable seccomp dynamic filesystem access control
     #[arg(long)]
     seccomp: bool,
@@ -78,6 +102,16 @@ struct Args {
     /// Allow KVM access for virtual machines
     #[arg(long)]
     allow_kvm: bool,
+
+    /// Enable eBPF "observe" mode: record every syscall and capability check the
+    /// workload makes instead of (or in addition to) blocking any of them via seccomp
+    #[arg(long)]
+    audit: bool,
+
+    /// Write the JSON audit report to this path when the sandbox exits (stdout if
+    /// omitted)
+    #[arg(long)]
+    audit_output: Option<String>,
 }
 
 #[tokio::main]
@@ -95,9 +129,17 @@ async fn main() -> anyhow::Result<()> {
 
     info!("Starting sandbox helper with args: {:?}", args);
 
+    // User namespaces are required to create the other namespaces unprivileged, so
+    // auto-enable them unless we're already root (where they're unnecessary).
+    let running_as_root = nix::unistd::geteuid().is_root();
+    let use_user_ns = !args.no_user_ns && (args.userns || !running_as_root);
+    if use_user_ns && !running_as_root {
+        info!("Running unprivileged: enabling user namespace with root-in-namespace uid/gid mapping");
+    }
+
     // Create sandbox configuration
     let namespace_config = NamespaceConfig {
-        user_ns: !args.no_user_ns,
+        user_ns: use_user_ns,
         mount_ns: !args.no_mount_ns,
         pid_ns: !args.no_pid_ns,
         uts_ns: true,
@@ -134,6 +176,26 @@ async fn main() -> anyhow::Result<()> {
     fs_config.static_mode = args.static_mode;
     fs_config.overlay_paths = args.overlay.clone();
     fs_config.blacklist_paths = args.blacklist.clone();
+    fs_config.ro_binds = args
+        .ro_bind
+        .iter()
+        .map(|spec| match spec.split_once(':') {
+            Some((src, dst)) => (src.to_string(), dst.to_string()),
+            None => (spec.clone(), spec.clone()),
+        })
+        .collect();
+    fs_config.mask_paths = args.mask_path.clone();
+    fs_config.sysctls = args
+        .sysctl
+        .iter()
+        .filter_map(|spec| match spec.split_once('=') {
+            Some((key, value)) => Some((key.to_string(), value.to_string())),
+            None => {
+                error!("Ignoring malformed --sysctl value (expected KEY=VALUE): {}", spec);
+                None
+            }
+        })
+        .collect();
 
     // Initialize sandbox with cgroups, seccomp, and networking enabled
     let mut sandbox = Sandbox::with_namespace_config(namespace_config)
@@ -180,6 +242,23 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    // Enable eBPF audit ("observe") mode if requested
+    //
+    // TODO: scope this to the sandbox's own cgroup once one is available pre-start();
+    // until then it audits every process on the host, which is only appropriate for
+    // local profiling runs, not production use.
+    if args.audit {
+        use sandbox_core::AuditConfig;
+
+        let audit_config = AuditConfig {
+            target_cgroup_id: 0,
+            output_path: args.audit_output.clone().map(std::path::PathBuf::from),
+        };
+
+        sandbox = sandbox.with_audit(audit_config);
+        info!("eBPF syscall/capability audit mode enabled");
+    }
+
     let fs_manager = FilesystemManager::with_config(fs_config);
 
     // Start sandbox (enter all namespaces in single unshare() call)
@@ -201,7 +280,28 @@ async fn main() -> anyhow::Result<()> {
 
     // Execute the process as PID 1 in child process, wait for completion
     // Parent process will return after child completes
-    match sandbox.exec_process() {
+    let result = sandbox.exec_process();
+
+    if args.audit {
+        match sandbox.collect_audit_report() {
+            Ok(Some(report)) => {
+                if args.audit_output.is_none() {
+                    if let Ok(json) = serde_json::to_string_pretty(&report) {
+                        println!("{}", json);
+                    }
+                }
+                info!(
+                    "Audit report: {} distinct syscalls, {} distinct capabilities checked",
+                    report.syscalls.len(),
+                    report.capabilities.len()
+                );
+            }
+            Ok(None) => {}
+            Err(e) => error!("Failed to collect audit report: {}", e),
+        }
+    }
+
+    match result {
         Ok(_) => {
             info!("Sandbox execution completed successfully");
             std::process::exit(0);
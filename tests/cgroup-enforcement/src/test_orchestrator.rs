@@ -1,7 +1,30 @@
 //! Test orchestrator for cgroup enforcement E2E tests
 //! This program launches the sandbox with abusive processes and verifies
 //! that cgroup limits are actually enforced.
+//!
+//! Verification reads the sandboxed process's cgroup v2 control files
+//! directly (resolved from `/proc/<pid>/cgroup`) rather than inferring
+//! enforcement from a timeout or a non-zero exit code: those are only
+//! heuristics and can't distinguish real enforcement from an unrelated
+//! crash. Each test samples the relevant counters on the same 100ms
+//! monitoring loop that watches the child, and the final sample is printed
+//! as JSON so CI can assert on the concrete numbers instead of a pass/fail
+//! bit.
+//!
+//! When eBPF tracing is available, each test also attaches
+//! `sandbox-resource-trace` to the sandbox's cgroup to count the underlying
+//! kernel events (forks, OOM kills, CFS throttles) directly, corroborating
+//! the cgroup-file counters with the kernel events that produced them. Like
+//! the cgroup-file reads, this is best-effort: a kernel without `CAP_BPF`/BTF
+//! support just runs without the corroborating counts, exactly as
+//! `sandbox_core::Sandbox::start()` treats a failed audit/resource-trace
+//! attach as non-fatal.
 
+use sandbox_resource_trace::{ResourceTraceConfig, ResourceTraceManager, ResourceTraceReport};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -39,10 +62,189 @@ impl TestType {
     }
 }
 
-fn run_enforcement_test(
+/// Cgroup v2 counters sampled over the lifetime of one enforcement test.
+/// `Option` fields are `None` when the control file was missing or held the
+/// literal `"max"` (cgroup v2's spelling of "unlimited").
+#[derive(Debug, Default, Clone, Serialize)]
+struct CgroupSnapshot {
+    pids_current: Option<u64>,
+    pids_max: Option<u64>,
+    /// `pids.events:max` — number of times a fork was refused because
+    /// `pids.current` would have exceeded `pids.max`.
+    pids_events_max: Option<u64>,
+    memory_current: Option<u64>,
+    memory_max: Option<u64>,
+    /// `memory.events:oom_kill` — number of times the OOM killer acted
+    /// inside this cgroup.
+    memory_oom_kill: Option<u64>,
+    /// `cpu.stat:throttled_usec`, cumulative microseconds spent throttled.
+    cpu_throttled_usec: Option<u64>,
+    /// `cpu.stat:nr_throttled`, cumulative number of throttled periods.
+    cpu_nr_throttled: Option<u64>,
+}
+
+impl CgroupSnapshot {
+    fn sample(cgroup_path: &Path) -> Self {
+        let pids_events = read_keyed_file(&cgroup_path.join("pids.events"));
+        let memory_events = read_keyed_file(&cgroup_path.join("memory.events"));
+        let cpu_stat = read_keyed_file(&cgroup_path.join("cpu.stat"));
+
+        Self {
+            pids_current: read_u64_file(&cgroup_path.join("pids.current")),
+            pids_max: read_limit_file(&cgroup_path.join("pids.max")),
+            pids_events_max: pids_events.get("max").copied(),
+            memory_current: read_u64_file(&cgroup_path.join("memory.current")),
+            memory_max: read_limit_file(&cgroup_path.join("memory.max")),
+            memory_oom_kill: memory_events.get("oom_kill").copied(),
+            cpu_throttled_usec: cpu_stat.get("throttled_usec").copied(),
+            cpu_nr_throttled: cpu_stat.get("nr_throttled").copied(),
+        }
+    }
+}
+
+/// Resolve the cgroup v2 unified-hierarchy path of `pid` from
+/// `/proc/<pid>/cgroup`, whose single line looks like `0::/<relative-path>`.
+fn resolve_cgroup_path(pid: u32) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(format!("/proc/{pid}/cgroup")).ok()?;
+    let relative = content.trim().rsplit_once(':')?.1;
+    Some(PathBuf::from("/sys/fs/cgroup").join(relative.trim_start_matches('/')))
+}
+
+/// Poll for up to a second for `pid`'s cgroup to show up: `sbx-helper` only
+/// moves itself into its cgroup after entering its namespaces, which
+/// happens a beat after the orchestrator observes the spawned PID.
+fn wait_for_cgroup_path(pid: u32) -> Option<PathBuf> {
+    for _ in 0..20 {
+        if let Some(path) = resolve_cgroup_path(pid) {
+            if path.join("cgroup.procs").exists() {
+                return Some(path);
+            }
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    None
+}
+
+/// The kernel identifies a cgroup (e.g. from `bpf_get_current_cgroup_id()`) by
+/// the inode number of its directory in the cgroup v2 filesystem, not by its
+/// path - resolve that so `ResourceTraceConfig::target_cgroup_id` scopes to
+/// the right cgroup.
+fn cgroup_id(cgroup_path: &Path) -> Option<u64> {
+    std::fs::metadata(cgroup_path).ok().map(|meta| meta.ino())
+}
+
+fn read_u64_file(path: &Path) -> Option<u64> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// Read a `*.max`-style file, which holds either a number or the literal
+/// `"max"` (cgroup v2's spelling of "unlimited", reported here as `None`).
+fn read_limit_file(path: &Path) -> Option<u64> {
+    let content = std::fs::read_to_string(path).ok()?;
+    content.trim().parse().ok()
+}
+
+/// Read a `key value` per-line file (`pids.events`, `memory.events`,
+/// `cpu.stat`) into a map.
+fn read_keyed_file(path: &Path) -> HashMap<String, u64> {
+    let mut values = HashMap::new();
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return values;
+    };
+    for line in content.lines() {
+        if let Some((key, value)) = line.split_once(' ') {
+            if let Ok(value) = value.trim().parse() {
+                values.insert(key.to_string(), value);
+            }
+        }
+    }
+    values
+}
+
+/// Result of verifying one enforcement test against its cgroup counters,
+/// printed as JSON so CI can assert on the raw numbers.
+#[derive(Debug, Serialize)]
+struct EnforcementResult {
+    test: &'static str,
+    passed: bool,
+    reason: String,
+    cgroup_resolved: bool,
+    peak: CgroupSnapshot,
+    #[serde(rename = "final")]
+    final_snapshot: CgroupSnapshot,
+    /// Kernel-level corroboration from `sandbox-resource-trace`, if eBPF
+    /// tracing was available on this kernel.
+    resource_trace: Option<ResourceTraceReport>,
+}
+
+/// Whether the eBPF-traced kernel events corroborate enforcement for
+/// `test_type`: the specific event each limit produces actually fired.
+fn trace_corroborates(test_type: &TestType, report: &ResourceTraceReport) -> bool {
+    match test_type {
+        TestType::ForkBomb => report.fork_count > 0,
+        TestType::MemoryHog => report.oom_kill_count > 0,
+        TestType::CpuBurner => report.throttle_count > 0,
+    }
+}
+
+/// Check the sampled counters for `test_type` against what enforcement
+/// should have produced, per the limit each abusive binary is meant to hit.
+fn verify_enforcement(
+    test_type: &TestType,
+    peak: &CgroupSnapshot,
+    final_snapshot: &CgroupSnapshot,
+) -> (bool, String) {
+    match test_type {
+        TestType::ForkBomb => {
+            let within_limit = match (peak.pids_current, final_snapshot.pids_max) {
+                (Some(current), Some(max)) => current <= max,
+                // No limit observed to check against; don't fail the test
+                // on a missing file, just say so via `reason`.
+                _ => true,
+            };
+            let limit_was_hit = final_snapshot.pids_events_max.unwrap_or(0) > 0;
+            (
+                within_limit && limit_was_hit,
+                format!(
+                    "pids.current peaked at {:?} against pids.max {:?}; pids.events:max = {:?}",
+                    peak.pids_current, final_snapshot.pids_max, final_snapshot.pids_events_max
+                ),
+            )
+        }
+        TestType::MemoryHog => {
+            let oom_killed = final_snapshot.memory_oom_kill.unwrap_or(0) > 0;
+            let within_limit = match (final_snapshot.memory_current, final_snapshot.memory_max) {
+                (Some(current), Some(max)) => current <= max,
+                _ => true,
+            };
+            (
+                oom_killed && within_limit,
+                format!(
+                    "memory.events:oom_kill = {:?}; memory.current {:?} vs memory.max {:?}",
+                    final_snapshot.memory_oom_kill,
+                    final_snapshot.memory_current,
+                    final_snapshot.memory_max
+                ),
+            )
+        }
+        TestType::CpuBurner => {
+            let was_throttled = final_snapshot.cpu_nr_throttled.unwrap_or(0) > 0;
+            let usec_increased = final_snapshot.cpu_throttled_usec.unwrap_or(0) > 0;
+            (
+                was_throttled && usec_increased,
+                format!(
+                    "cpu.stat:nr_throttled = {:?}, throttled_usec = {:?}",
+                    final_snapshot.cpu_nr_throttled, final_snapshot.cpu_throttled_usec
+                ),
+            )
+        }
+    }
+}
+
+async fn run_enforcement_test(
     test_type: TestType,
     sbx_helper_path: &std::path::Path,
-) -> Result<(), Box<dyn std::error::Error>> {
+) -> Result<EnforcementResult, Box<dyn std::error::Error>> {
     println!("🧪 Running {} test", test_type.description());
     println!("   Binary: {}", test_type.binary_name());
     println!("   Timeout: {:.1}s", test_type.timeout().as_secs_f64());
@@ -61,72 +263,146 @@ fn run_enforcement_test(
 
     println!("   Command: {:?}", cmd);
 
-    match cmd.spawn() {
-        Ok(mut child) => {
-            println!("✅ Sandbox process started (PID: {})", child.id());
-
-            // Monitor the process
-            let timeout = test_type.timeout();
-            let mut last_check = Instant::now();
-
-            loop {
-                // Check if process is still running
-                match child.try_wait() {
-                    Ok(Some(status)) => {
-                        let elapsed = start_time.elapsed();
-                        println!(
-                            "✅ Process completed in {:.2}s with exit code: {}",
-                            elapsed.as_secs_f64(),
-                            status.code().unwrap_or(-1)
-                        );
-
-                        if status.success() {
-                            println!("✅ Test PASSED - process completed normally");
-                        } else {
-                            println!("⚠️  Test UNCLEAR - process exited with error (may indicate limits enforced)");
-                        }
-                        return Ok(());
-                    }
-                    Ok(None) => {
-                        // Process still running, check timeout
-                        if start_time.elapsed() > timeout {
-                            println!(
-                                "⏰ Process timed out after {:.2}s - terminating",
-                                timeout.as_secs_f64()
-                            );
-                            let _ = child.kill();
-                            println!(
-                                "✅ Test PASSED - process was contained (didn't run indefinitely)"
-                            );
-                            return Ok(());
-                        }
-
-                        // Periodic monitoring
-                        if last_check.elapsed() > Duration::from_secs(1) {
-                            println!(
-                                "   Process still running... ({:.1}s elapsed)",
-                                start_time.elapsed().as_secs_f64()
-                            );
-                            last_check = Instant::now();
-                        }
-
-                        thread::sleep(Duration::from_millis(100));
-                    }
-                    Err(e) => {
-                        println!("❌ Error checking process status: {}", e);
-                        return Err(e.into());
-                    }
+    let mut child = cmd.spawn()?;
+    println!("✅ Sandbox process started (PID: {})", child.id());
+
+    let cgroup_path = wait_for_cgroup_path(child.id());
+    match &cgroup_path {
+        Some(path) => println!("   Resolved cgroup at {:?}", path),
+        None => println!("⚠️  Could not resolve cgroup path from /proc/{}/cgroup", child.id()),
+    }
+
+    // Best-effort: attach eBPF resource tracing scoped to this cgroup. A
+    // kernel without CAP_BPF/BTF support just means no corroborating counts,
+    // same as `sandbox_core::Sandbox::start()` treats a failed attach.
+    let mut resource_trace_manager = match cgroup_path.as_deref().and_then(cgroup_id) {
+        Some(id) => {
+            let mut manager = ResourceTraceManager::new(ResourceTraceConfig {
+                target_cgroup_id: id,
+                output_path: None,
+            });
+            match manager.start().await {
+                Ok(()) => Some(manager),
+                Err(e) => {
+                    println!("⚠️  eBPF resource tracing unavailable: {}", e);
+                    None
                 }
             }
         }
-        Err(e) => {
-            println!("❌ Failed to start sandbox process: {}", e);
-            Err(e.into())
+        None => None,
+    };
+
+    let timeout = test_type.timeout();
+    let mut last_check = Instant::now();
+    let mut peak = CgroupSnapshot::default();
+    let mut latest = CgroupSnapshot::default();
+
+    loop {
+        if let Some(path) = &cgroup_path {
+            latest = CgroupSnapshot::sample(path);
+            peak.pids_current = peak.pids_current.max(latest.pids_current);
+            peak.memory_current = peak.memory_current.max(latest.memory_current);
+        }
+
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                let elapsed = start_time.elapsed();
+                println!(
+                    "✅ Process completed in {:.2}s with exit code: {}",
+                    elapsed.as_secs_f64(),
+                    status.code().unwrap_or(-1)
+                );
+                break;
+            }
+            Ok(None) => {
+                if start_time.elapsed() > timeout {
+                    println!(
+                        "⏰ Process timed out after {:.2}s - terminating",
+                        timeout.as_secs_f64()
+                    );
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    break;
+                }
+
+                if last_check.elapsed() > Duration::from_secs(1) {
+                    println!(
+                        "   Process still running... ({:.1}s elapsed)",
+                        start_time.elapsed().as_secs_f64()
+                    );
+                    last_check = Instant::now();
+                }
+
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            Err(e) => {
+                println!("❌ Error checking process status: {}", e);
+                return Err(e.into());
+            }
         }
     }
+
+    // One last sample: the cgroup directory is usually still around
+    // immediately after the child exits, but won't be once sbx-helper's
+    // cleanup (or ours, on timeout) removes it.
+    if let Some(path) = &cgroup_path {
+        latest = CgroupSnapshot::sample(path);
+        peak.pids_current = peak.pids_current.max(latest.pids_current);
+        peak.memory_current = peak.memory_current.max(latest.memory_current);
+    }
+
+    let resource_trace = match &mut resource_trace_manager {
+        Some(manager) => manager.stop().ok(),
+        None => None,
+    };
+
+    let (mut passed, mut reason) = if cgroup_path.is_some() {
+        verify_enforcement(&test_type, &peak, &latest)
+    } else {
+        (
+            false,
+            "cgroup path could not be resolved; enforcement could not be verified".to_string(),
+        )
+    };
+
+    if let Some(report) = &resource_trace {
+        let corroborated = trace_corroborates(&test_type, report);
+        reason = format!(
+            "{reason}; eBPF trace: {} forks, {} OOM kills, {} throttles ({})",
+            report.fork_count,
+            report.oom_kill_count,
+            report.throttle_count,
+            if corroborated { "corroborates" } else { "does not corroborate" }
+        );
+        passed = passed && corroborated;
+    }
+
+    let result = EnforcementResult {
+        test: test_type.binary_name(),
+        passed,
+        reason,
+        cgroup_resolved: cgroup_path.is_some(),
+        peak,
+        final_snapshot: latest,
+        resource_trace,
+    };
+
+    println!(
+        "{} {}",
+        if result.passed { "✅" } else { "❌" },
+        if result.passed {
+            "Test PASSED - enforcement verified via cgroup counters"
+        } else {
+            "Test FAILED - cgroup counters don't show enforcement"
+        }
+    );
+    println!("{}", serde_json::to_string_pretty(&result)?);
+
+    Ok(result)
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
+#[tokio::main(flavor = "multi_thread")]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 Cgroup Enforcement Test Orchestrator");
     println!("=====================================");
 
@@ -153,22 +429,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Run tests
     let tests = vec![TestType::ForkBomb, TestType::MemoryHog, TestType::CpuBurner];
 
-    let mut passed = 0;
-    let mut failed = 0;
-
+    let mut results = Vec::new();
     for test in tests {
         println!();
-        match run_enforcement_test(test, &sbx_helper_path) {
-            Ok(()) => {
-                passed += 1;
-            }
+        match run_enforcement_test(test, &sbx_helper_path).await {
+            Ok(result) => results.push(result),
             Err(e) => {
-                println!("❌ Test failed: {}", e);
-                failed += 1;
+                println!("❌ Test failed to run: {}", e);
+                return Err(e);
             }
         }
     }
 
+    let passed = results.iter().filter(|r| r.passed).count();
+    let failed = results.len() - passed;
+
     println!();
     println!("📊 Test Results:");
     println!("   Passed: {}", passed);
@@ -0,0 +1,66 @@
+//! Binary for testing masked-path enforcement.
+//!
+//! This program reads a path that should have been masked by sbx-helper (`/dev/null`
+//! bound over a file, or an empty read-only tmpfs mounted over a directory) and
+//! verifies that it is empty.
+
+use clap::Parser;
+use std::fs;
+use std::process;
+use tracing::{error, info};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Path expected to be masked
+    #[arg(long)]
+    path: String,
+}
+
+fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let args = Args::parse();
+
+    info!("Reading masked path {}", args.path);
+
+    let metadata = match fs::metadata(&args.path) {
+        Ok(m) => m,
+        Err(e) => {
+            error!("Failed to stat {}: {}", args.path, e);
+            process::exit(2);
+        }
+    };
+
+    if metadata.is_dir() {
+        match fs::read_dir(&args.path) {
+            Ok(mut entries) => {
+                if entries.next().is_some() {
+                    error!("Masked directory {} is not empty", args.path);
+                    process::exit(1);
+                }
+                info!("Masked directory {} is empty as expected", args.path);
+                Ok(())
+            }
+            Err(e) => {
+                error!("Failed to read masked directory {}: {}", args.path, e);
+                process::exit(2);
+            }
+        }
+    } else {
+        match fs::read(&args.path) {
+            Ok(contents) if contents.is_empty() => {
+                info!("Masked file {} reads as empty as expected", args.path);
+                Ok(())
+            }
+            Ok(_) => {
+                error!("Masked file {} is not empty", args.path);
+                process::exit(1);
+            }
+            Err(e) => {
+                error!("Failed to read masked file {}: {}", args.path, e);
+                process::exit(2);
+            }
+        }
+    }
+}
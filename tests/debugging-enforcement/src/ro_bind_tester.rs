@@ -0,0 +1,40 @@
+//! Binary for testing read-only bind mount enforcement.
+//!
+//! This program attempts to write to a path that should have been hardened with
+//! `--ro-bind` by sbx-helper. The write is expected to fail with EROFS.
+
+use clap::Parser;
+use std::fs;
+use std::process;
+use tracing::{error, info};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Path to attempt writing to (expected to be a read-only bind mount)
+    #[arg(long)]
+    path: String,
+}
+
+fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let args = Args::parse();
+
+    info!("Attempting to write to {}", args.path);
+
+    match fs::write(&args.path, b"ro-bind-enforcement-test") {
+        Ok(()) => {
+            error!("Write to {} unexpectedly succeeded", args.path);
+            process::exit(1);
+        }
+        Err(e) if e.raw_os_error() == Some(libc::EROFS) => {
+            info!("Write correctly failed with EROFS");
+            Ok(())
+        }
+        Err(e) => {
+            error!("Write failed with unexpected error: {}", e);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+//! Binary for testing sysctl hardening enforcement.
+//!
+//! This program reads a sysctl value that sbx-helper should have set via `--sysctl`
+//! and verifies it matches the expected value.
+
+use clap::Parser;
+use std::fs;
+use std::process;
+use tracing::{error, info};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Dotted sysctl key (e.g. `net.ipv4.ip_forward`)
+    #[arg(long)]
+    key: String,
+
+    /// Value expected to have been set for this key
+    #[arg(long)]
+    expected: String,
+}
+
+fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let args = Args::parse();
+    let proc_path = format!("/proc/sys/{}", args.key.replace('.', "/"));
+
+    info!("Reading sysctl {} from {}", args.key, proc_path);
+
+    match fs::read_to_string(&proc_path) {
+        Ok(contents) if contents.trim() == args.expected => {
+            info!("Sysctl {} matches expected value {}", args.key, args.expected);
+            Ok(())
+        }
+        Ok(contents) => {
+            error!(
+                "Sysctl {} is {:?}, expected {:?}",
+                args.key,
+                contents.trim(),
+                args.expected
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            error!("Failed to read sysctl {} from {}: {}", args.key, proc_path, e);
+            process::exit(2);
+        }
+    }
+}
@@ -1,15 +1,31 @@
 //! Test orchestrator for debugging enforcement tests.
 //!
-//! This program coordinates E2E tests for debugging functionality:
+//! This is a small parallel test harness, not just a runner for a fixed list: test
+//! cases are registered in a table, executed across a worker pool (`--jobs`), and can
+//! be narrowed with `--filter <substring>` or split across CI machines with
+//! `--shard i/N` (a stable hash of the test name modulo N). Flaky namespace setup can
+//! be retried with `--retries K`. Results are reported as human-readable text, a JSON
+//! summary, and/or a JUnit XML file via `--reporter`, so CI can ingest them directly.
+//!
+//! Covered scenarios:
 //! - gdb attach works in debug mode
 //! - gdb attach fails in normal mode
 //! - host processes are invisible from sandbox
+//! - --ro-bind paths reject writes with EROFS
+//! - --mask-path paths read back empty
+//! - --sysctl values are observable inside the sandbox
+//! - --audit produces a non-empty syscall/capability report
 
 use clap::Parser;
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
 use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
-use tracing::{error, info};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -17,6 +33,115 @@ struct Args {
     /// Path to sbx-helper binary
     #[arg(long, default_value = "../../target/debug/sbx-helper")]
     sbx_helper_path: String,
+
+    /// Number of test cases to run concurrently
+    #[arg(long, default_value_t = 1)]
+    jobs: usize,
+
+    /// Only run test cases whose name contains this substring
+    #[arg(long)]
+    filter: Option<String>,
+
+    /// Run only the i-th of N shards (1-indexed, e.g. `2/4`), split by a stable hash
+    /// of the test name so the assignment is consistent across CI machines
+    #[arg(long)]
+    shard: Option<String>,
+
+    /// Retry a failed test case up to this many additional times before recording it
+    /// as FAILED (namespace setup under load can be flaky)
+    #[arg(long, default_value_t = 0)]
+    retries: usize,
+
+    /// How to report results
+    #[arg(long, value_enum, default_value_t = Reporter::Text)]
+    reporter: Reporter,
+
+    /// Directory to write the JSON/JUnit report files into
+    #[arg(long, default_value = "test-results")]
+    report_dir: String,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum Reporter {
+    /// Human-readable summary on stdout only
+    Text,
+    /// Text summary plus a `results.json` file
+    Json,
+    /// Text summary plus a `results.xml` JUnit file
+    Junit,
+    /// Text summary plus both JSON and JUnit files
+    All,
+}
+
+/// Outcome of a single test case invocation, including captured child process output
+/// so a failure can be diagnosed from the report alone.
+struct TestOutcome {
+    passed: bool,
+    skipped: bool,
+    stdout: String,
+    stderr: String,
+}
+
+impl TestOutcome {
+    fn pass() -> Self {
+        Self { passed: true, skipped: false, stdout: String::new(), stderr: String::new() }
+    }
+
+    fn fail() -> Self {
+        Self { passed: false, skipped: false, stdout: String::new(), stderr: String::new() }
+    }
+
+    fn skip() -> Self {
+        Self { passed: true, skipped: true, stdout: String::new(), stderr: String::new() }
+    }
+
+    fn with_output(mut self, stdout: Vec<u8>, stderr: Vec<u8>) -> Self {
+        self.stdout = String::from_utf8_lossy(&stdout).into_owned();
+        self.stderr = String::from_utf8_lossy(&stderr).into_owned();
+        self
+    }
+}
+
+/// Everything a test case needs to spawn sbx-helper and decide skip-vs-fail.
+struct TestContext<'a> {
+    sbx_helper: &'a str,
+    userns_available: bool,
+    test_name: &'static str,
+    /// Which attempt this is (0 = first try), used only to keep coverage profile
+    /// and audit report filenames distinct across retries.
+    attempt: usize,
+}
+
+struct TestCase {
+    name: &'static str,
+    run: fn(&TestContext) -> TestOutcome,
+}
+
+const TEST_CASES: &[TestCase] = &[
+    TestCase { name: "ptrace_debug_mode", run: test_ptrace_in_debug_mode },
+    TestCase { name: "ptrace_normal_mode", run: test_ptrace_in_normal_mode },
+    TestCase { name: "host_process_isolation", run: test_host_process_isolation },
+    TestCase { name: "ro_bind_enforcement", run: test_ro_bind_enforcement },
+    TestCase { name: "masked_path_enforcement", run: test_masked_path_enforcement },
+    TestCase { name: "sysctl_enforcement", run: test_sysctl_enforcement },
+    TestCase { name: "audit_observe_mode", run: test_audit_observe_mode },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Status {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TestResult {
+    name: String,
+    status: Status,
+    duration_secs: f64,
+    stdout: String,
+    stderr: String,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -35,39 +160,132 @@ fn main() -> anyhow::Result<()> {
     info!("Starting debugging enforcement tests");
     info!("Using sbx-helper at: {}", sbx_helper);
 
-    let mut results = Vec::new();
+    // Unprivileged user namespaces let sbx-helper create the other namespaces without
+    // root, so once they're available there's no excuse for these tests to be skipped:
+    // a sandbox that still exits with a permission error is a real failure, not a skip.
+    let userns_available = user_namespaces_available();
+    info!("Unprivileged user namespaces available: {}", userns_available);
+
+    let shard = args.shard.as_deref().map(parse_shard).transpose()?;
+
+    let selected: Vec<&'static TestCase> = TEST_CASES
+        .iter()
+        .filter(|tc| args.filter.as_deref().map_or(true, |f| tc.name.contains(f)))
+        .filter(|tc| shard.map_or(true, |s| in_shard(tc.name, s)))
+        .collect();
 
-    // Test 1: gdb attach should work in debug mode
-    info!("Test 1: Testing ptrace attach in debug mode (--seccomp --seccomp-debug)");
-    let (result1, skipped1) = test_ptrace_in_debug_mode(&sbx_helper);
-    results.push(("ptrace_debug_mode", result1, skipped1));
+    if selected.is_empty() {
+        warn!("No test cases selected (check --filter/--shard)");
+    }
+    info!("Running {} test case(s) across {} worker(s)", selected.len(), args.jobs.max(1));
+
+    let queue = Arc::new(Mutex::new(VecDeque::from(selected)));
+    let results = Arc::new(Mutex::new(Vec::<TestResult>::new()));
+    let jobs = args.jobs.max(1);
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let queue = Arc::clone(&queue);
+            let results = Arc::clone(&results);
+            let sbx_helper = sbx_helper.clone();
+            scope.spawn(move || loop {
+                let test_case = {
+                    let mut queue = queue.lock().unwrap();
+                    match queue.pop_front() {
+                        Some(tc) => tc,
+                        None => return,
+                    }
+                };
+
+                let result = run_with_retries(&sbx_helper, userns_available, test_case, args.retries);
+                results.lock().unwrap().push(result);
+            });
+        }
+    });
 
-    // Test 2: gdb attach should fail in normal mode
-    info!("Test 2: Testing ptrace attach in normal mode (--seccomp)");
-    let (result2, skipped2) = test_ptrace_in_normal_mode(&sbx_helper);
-    results.push(("ptrace_normal_mode", result2, skipped2));
+    let mut results = Arc::try_unwrap(results).unwrap().into_inner().unwrap();
+    results.sort_by(|a, b| a.name.cmp(&b.name));
 
-    // Test 3: host processes should be invisible from sandbox
-    info!("Test 3: Testing host process isolation");
-    let (result3, skipped3) = test_host_process_isolation(&sbx_helper);
-    results.push(("host_process_isolation", result3, skipped3));
+    report_text(&results);
 
-    // Report results
+    match args.reporter {
+        Reporter::Text => {}
+        Reporter::Json => write_json_report(&args.report_dir, &results)?,
+        Reporter::Junit => write_junit_report(&args.report_dir, &results)?,
+        Reporter::All => {
+            write_json_report(&args.report_dir, &results)?;
+            write_junit_report(&args.report_dir, &results)?;
+        }
+    }
+
+    if results.iter().any(|r| r.status == Status::Failed) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Run a test case, retrying up to `retries` additional times on failure. Skips are
+/// never retried - they're not a transient condition, they mean the environment
+/// genuinely can't exercise this test.
+fn run_with_retries(
+    sbx_helper: &str,
+    userns_available: bool,
+    test_case: &'static TestCase,
+    retries: usize,
+) -> TestResult {
+    let mut attempt = 0;
+    let start = Instant::now();
+
+    loop {
+        let ctx = TestContext { sbx_helper, userns_available, test_name: test_case.name, attempt };
+        let outcome = (test_case.run)(&ctx);
+        let finished = outcome.skipped || outcome.passed || attempt >= retries;
+
+        if !finished {
+            warn!("{}: attempt {} failed, retrying", test_case.name, attempt + 1);
+            attempt += 1;
+            continue;
+        }
+
+        let status = if outcome.skipped {
+            Status::Skipped
+        } else if outcome.passed {
+            Status::Passed
+        } else {
+            Status::Failed
+        };
+
+        return TestResult {
+            name: test_case.name.to_string(),
+            status,
+            duration_secs: start.elapsed().as_secs_f64(),
+            stdout: outcome.stdout,
+            stderr: outcome.stderr,
+        };
+    }
+}
+
+fn report_text(results: &[TestResult]) {
     info!("=== Test Results ===");
     let mut passed = 0;
     let mut failed = 0;
     let mut skipped = 0;
 
-    for (name, success, is_skipped) in &results {
-        if *is_skipped {
-            info!("⚠️  {}: SKIPPED (insufficient privileges)", name);
-            skipped += 1;
-        } else if *success {
-            info!("✅ {}: PASSED", name);
-            passed += 1;
-        } else {
-            error!("❌ {}: FAILED", name);
-            failed += 1;
+    for result in results {
+        match result.status {
+            Status::Skipped => {
+                info!("⚠️  {}: SKIPPED ({:.2}s)", result.name, result.duration_secs);
+                skipped += 1;
+            }
+            Status::Passed => {
+                info!("✅ {}: PASSED ({:.2}s)", result.name, result.duration_secs);
+                passed += 1;
+            }
+            Status::Failed => {
+                error!("❌ {}: FAILED ({:.2}s)", result.name, result.duration_secs);
+                failed += 1;
+            }
         }
     }
 
@@ -77,18 +295,159 @@ fn main() -> anyhow::Result<()> {
         passed + failed + skipped,
         skipped
     );
+}
 
-    if failed > 0 {
-        std::process::exit(1);
+fn write_json_report(report_dir: &str, results: &[TestResult]) -> anyhow::Result<()> {
+    std::fs::create_dir_all(report_dir)?;
+    let path = std::path::Path::new(report_dir).join("results.json");
+    let json = serde_json::to_string_pretty(results)?;
+    std::fs::write(&path, json)?;
+    info!("Wrote JSON report to {}", path.display());
+    Ok(())
+}
+
+fn write_junit_report(report_dir: &str, results: &[TestResult]) -> anyhow::Result<()> {
+    std::fs::create_dir_all(report_dir)?;
+    let path = std::path::Path::new(report_dir).join("results.xml");
+
+    let failures = results.iter().filter(|r| r.status == Status::Failed).count();
+    let skipped = results.iter().filter(|r| r.status == Status::Skipped).count();
+
+    let mut xml = String::new();
+    xml.push_str(&format!(
+        "<testsuite name=\"debugging-enforcement\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">\n",
+        results.len(),
+        failures,
+        skipped
+    ));
+    for result in results {
+        xml.push_str(&format!(
+            "  <testcase name=\"{}\" classname=\"debugging-enforcement\" time=\"{:.3}\">\n",
+            xml_escape(&result.name),
+            result.duration_secs
+        ));
+        match result.status {
+            Status::Skipped => xml.push_str("    <skipped/>\n"),
+            Status::Failed => xml.push_str("    <failure/>\n"),
+            Status::Passed => {}
+        }
+        xml.push_str(&format!(
+            "    <system-out>{}</system-out>\n    <system-err>{}</system-err>\n",
+            xml_escape(&result.stdout),
+            xml_escape(&result.stderr)
+        ));
+        xml.push_str("  </testcase>\n");
     }
+    xml.push_str("</testsuite>\n");
 
+    std::fs::write(&path, xml)?;
+    info!("Wrote JUnit report to {}", path.display());
     Ok(())
 }
 
-fn test_ptrace_in_debug_mode(sbx_helper: &str) -> (bool, bool) {
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Parse a `--shard i/N` value into a zero-based `(index, total)` pair.
+fn parse_shard(spec: &str) -> anyhow::Result<(usize, usize)> {
+    let (index, total) = spec
+        .split_once('/')
+        .ok_or_else(|| anyhow::anyhow!("--shard must be of the form i/N, got {:?}", spec))?;
+    let index: usize = index.parse()?;
+    let total: usize = total.parse()?;
+    if total == 0 || index == 0 || index > total {
+        anyhow::bail!("--shard i/N requires 1 <= i <= N, got {:?}", spec);
+    }
+    Ok((index - 1, total))
+}
+
+/// Stable (not dependent on process/hash-seed randomization) shard assignment: hash
+/// the test name and take it modulo the shard count.
+fn in_shard(name: &str, (index, total): (usize, usize)) -> bool {
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    (hasher.finish() as usize % total) == index
+}
+
+/// Build a `Command` for sbx-helper with a per-test, per-attempt `LLVM_PROFILE_FILE`
+/// so coverage from sandboxed children doesn't collide across parallel/retried runs;
+/// the resulting .profraw files can be merged into lcov with `llvm-profdata`/`grcov`
+/// the same way any other integrated-coverage test runner does it.
+fn helper_command(ctx: &TestContext) -> Command {
+    let mut cmd = Command::new(ctx.sbx_helper);
+    if let Some(profile_file) = coverage_profile_path(ctx.test_name, ctx.attempt) {
+        cmd.env("LLVM_PROFILE_FILE", profile_file);
+    }
+    cmd
+}
+
+fn coverage_profile_path(test_name: &str, attempt: usize) -> Option<String> {
+    let base = std::env::var("LLVM_PROFILE_FILE").ok()?;
+    let dir = std::path::Path::new(&base)
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_else(|| std::path::PathBuf::from("."));
+    let safe_name: String =
+        test_name.chars().map(|c| if c.is_alphanumeric() || c == '-' { c } else { '_' }).collect();
+    Some(
+        dir.join(format!("{}-attempt{}-%p.profraw", safe_name, attempt))
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+/// Check whether this kernel/environment permits unprivileged user namespace creation.
+///
+/// We fork a throwaway child and have it attempt `unshare(CLONE_NEWUSER)` so we don't
+/// disturb our own namespaces; the child's exit status tells us whether the call succeeded.
+fn user_namespaces_available() -> bool {
+    use nix::sched::{unshare, CloneFlags};
+    use nix::sys::wait::{waitpid, WaitStatus};
+    use nix::unistd::{fork, ForkResult};
+
+    match unsafe { fork() } {
+        Ok(ForkResult::Child) => {
+            let ok = unshare(CloneFlags::CLONE_NEWUSER).is_ok();
+            std::process::exit(if ok { 0 } else { 1 });
+        }
+        Ok(ForkResult::Parent { child }) => matches!(
+            waitpid(child, None),
+            Ok(WaitStatus::Exited(_, 0))
+        ),
+        Err(e) => {
+            error!("Failed to fork while probing for user namespace support: {}", e);
+            false
+        }
+    }
+}
+
+/// Translate a sandbox exit code of 1 (insufficient privileges) into either a skip (when
+/// user namespaces aren't available in this environment) or a hard failure (when they are,
+/// since sbx-helper should have been able to create its own namespaces unprivileged).
+fn permission_error_outcome(userns_available: bool, context: &str) -> TestOutcome {
+    if userns_available {
+        error!(
+            "{}: sandbox exited with a permission error even though user namespaces are available",
+            context
+        );
+        TestOutcome::fail()
+    } else {
+        info!(
+            "⚠️  {}: skipped (user namespaces unavailable in this environment)",
+            context
+        );
+        TestOutcome::skip()
+    }
+}
+
+fn test_ptrace_in_debug_mode(ctx: &TestContext) -> TestOutcome {
     // Start a target process in the sandbox with debug mode enabled
     // The target will be a simple sleep process
-    let target_cmd = Command::new(sbx_helper)
+    let target_cmd = helper_command(ctx)
         .args(&[
             "--seccomp",
             "--seccomp-debug",
@@ -103,7 +462,7 @@ fn test_ptrace_in_debug_mode(sbx_helper: &str) -> (bool, bool) {
         Ok(p) => p,
         Err(e) => {
             error!("Failed to start target process in debug mode: {}", e);
-            return (false, false);
+            return TestOutcome::fail();
         }
     };
 
@@ -122,7 +481,7 @@ fn test_ptrace_in_debug_mode(sbx_helper: &str) -> (bool, bool) {
             "Target process exited before ptrace test with status: {}",
             status
         );
-        return (false, false);
+        return TestOutcome::fail();
     }
 
     // Check if the sandbox process is still running (not exited due to permission error)
@@ -130,18 +489,13 @@ fn test_ptrace_in_debug_mode(sbx_helper: &str) -> (bool, bool) {
         Ok(Some(status)) => {
             // Process has already exited - likely due to permission error
             if status.code() == Some(1) {
-                info!("⚠️  Sandbox exited with permission error (expected in unprivileged environment)");
-                info!(
-                    "   This test requires privileges to create namespaces and mount filesystems"
-                );
-                info!("   Skipping ptrace test in debug mode");
-                return (true, true); // (success=true, skipped=true)
+                return permission_error_outcome(ctx.userns_available, "target process (debug mode)");
             } else {
                 error!(
                     "Sandbox process exited unexpectedly with status: {}",
                     status
                 );
-                return (false, false);
+                return TestOutcome::fail();
             }
         }
         Ok(None) => {
@@ -151,12 +505,12 @@ fn test_ptrace_in_debug_mode(sbx_helper: &str) -> (bool, bool) {
         Err(e) => {
             error!("Failed to check sandbox process status: {}", e);
             let _ = target_process.kill();
-            return (false, false);
+            return TestOutcome::fail();
         }
     }
 
     // Now try to attach to it using our ptrace tester
-    let test_result = Command::new(sbx_helper)
+    let test_result = helper_command(ctx)
         .args(&[
             "--seccomp",
             "--seccomp-debug",
@@ -164,36 +518,34 @@ fn test_ptrace_in_debug_mode(sbx_helper: &str) -> (bool, bool) {
             "--target-pid",
             &target_pid.to_string(),
         ])
-        .status();
+        .output();
 
     // Clean up the target process
     let _ = target_process.kill();
 
     match test_result {
-        Ok(status) if status.success() => {
+        Ok(output) if output.status.success() => {
             info!("Ptrace attach succeeded in debug mode as expected");
-            (true, false) // (success=true, skipped=false)
+            TestOutcome::pass().with_output(output.stdout, output.stderr)
         }
-        Ok(status) if status.code() == Some(1) => {
+        Ok(output) if output.status.code() == Some(1) => {
             // Exit code 1 indicates sandbox creation failed due to permissions
-            info!("⚠️  Ptrace test in debug mode skipped due to insufficient privileges");
-            info!("   This test requires elevated privileges to create namespaces");
-            (true, true) // (success=true, skipped=true)
+            permission_error_outcome(ctx.userns_available, "ptrace test (debug mode)")
         }
-        Ok(status) => {
-            error!("Ptrace attach failed in debug mode with status: {}", status);
-            (false, false)
+        Ok(output) => {
+            error!("Ptrace attach failed in debug mode with status: {}", output.status);
+            TestOutcome::fail().with_output(output.stdout, output.stderr)
         }
         Err(e) => {
             error!("Failed to run ptrace test in debug mode: {}", e);
-            (false, false)
+            TestOutcome::fail()
         }
     }
 }
 
-fn test_ptrace_in_normal_mode(sbx_helper: &str) -> (bool, bool) {
+fn test_ptrace_in_normal_mode(ctx: &TestContext) -> TestOutcome {
     // Start a target process in the sandbox with normal mode (no debug)
-    let target_cmd = Command::new(sbx_helper)
+    let target_cmd = helper_command(ctx)
         .args(&[
             "--seccomp",
             "/nix/store/xbp2j3z0lhizr5vvzff4dgdcxgs8i2w7-coreutils-9.7/bin/sleep",
@@ -207,7 +559,7 @@ fn test_ptrace_in_normal_mode(sbx_helper: &str) -> (bool, bool) {
         Ok(p) => p,
         Err(e) => {
             error!("Failed to start target process in normal mode: {}", e);
-            return (false, false);
+            return TestOutcome::fail();
         }
     };
 
@@ -226,7 +578,7 @@ fn test_ptrace_in_normal_mode(sbx_helper: &str) -> (bool, bool) {
             "Target process exited before ptrace test with status: {}",
             status
         );
-        return (false, false);
+        return TestOutcome::fail();
     }
 
     // Check if the sandbox process is still running (not exited due to permission error)
@@ -234,18 +586,13 @@ fn test_ptrace_in_normal_mode(sbx_helper: &str) -> (bool, bool) {
         Ok(Some(status)) => {
             // Process has already exited - likely due to permission error
             if status.code() == Some(1) {
-                info!("⚠️  Sandbox exited with permission error (expected in unprivileged environment)");
-                info!(
-                    "   This test requires privileges to create namespaces and mount filesystems"
-                );
-                info!("   Skipping ptrace test in normal mode");
-                return (true, true); // (success=true, skipped=true)
+                return permission_error_outcome(ctx.userns_available, "target process (normal mode)");
             } else {
                 error!(
                     "Sandbox process exited unexpectedly with status: {}",
                     status
                 );
-                return (false, false);
+                return TestOutcome::fail();
             }
         }
         Ok(None) => {
@@ -255,56 +602,54 @@ fn test_ptrace_in_normal_mode(sbx_helper: &str) -> (bool, bool) {
         Err(e) => {
             error!("Failed to check sandbox process status: {}", e);
             let _ = target_process.kill();
-            return (false, false);
+            return TestOutcome::fail();
         }
     }
 
     // Now try to attach to it using our ptrace tester
-    let test_result = Command::new(sbx_helper)
+    let test_result = helper_command(ctx)
         .args(&[
             "--seccomp",
             "../../target/debug/ptrace_tester",
             "--target-pid",
             &target_pid.to_string(),
         ])
-        .status();
+        .output();
 
     // Clean up the target process
     let _ = target_process.kill();
 
     match test_result {
-        Ok(status) if status.code() == Some(2) => {
+        Ok(output) if output.status.code() == Some(2) => {
             // Exit code 2 means EPERM, which is expected
             info!("Ptrace attach correctly failed with EPERM in normal mode");
-            (true, false) // (success=true, skipped=false)
+            TestOutcome::pass().with_output(output.stdout, output.stderr)
         }
-        Ok(status) if status.code() == Some(1) => {
+        Ok(output) if output.status.code() == Some(1) => {
             // Exit code 1 indicates sandbox creation failed due to permissions
-            info!("⚠️  Ptrace test in normal mode skipped due to insufficient privileges");
-            info!("   This test requires elevated privileges to create namespaces");
-            (true, true) // (success=true, skipped=true)
+            permission_error_outcome(ctx.userns_available, "ptrace test (normal mode)")
         }
-        Ok(status) => {
+        Ok(output) => {
             error!(
                 "Ptrace attach failed with unexpected status in normal mode: {}",
-                status
+                output.status
             );
-            (false, false)
+            TestOutcome::fail().with_output(output.stdout, output.stderr)
         }
         Err(e) => {
             error!("Failed to run ptrace test in normal mode: {}", e);
-            (false, false)
+            TestOutcome::fail()
         }
     }
 }
 
-fn test_host_process_isolation(sbx_helper: &str) -> (bool, bool) {
+fn test_host_process_isolation(ctx: &TestContext) -> TestOutcome {
     // Get our own PID as a host process to test against
     let host_pid = std::process::id() as i32;
     info!("Testing isolation from host process {}", host_pid);
 
     // Try to ptrace the host process from within the sandbox
-    let test_result = Command::new(sbx_helper)
+    let test_result = helper_command(ctx)
         .args(&[
             "--seccomp",
             "--seccomp-debug",
@@ -312,26 +657,201 @@ fn test_host_process_isolation(sbx_helper: &str) -> (bool, bool) {
             "--host-pid",
             &host_pid.to_string(),
         ])
-        .status();
+        .output();
 
     match test_result {
-        Ok(status) if status.success() => {
+        Ok(output) if output.status.success() => {
             info!("Host process correctly isolated from sandbox");
-            (true, false) // (success=true, skipped=false)
+            TestOutcome::pass().with_output(output.stdout, output.stderr)
         }
-        Ok(status) if status.code() == Some(1) => {
+        Ok(output) if output.status.code() == Some(1) => {
             // Exit code 1 indicates sandbox creation failed due to permissions
-            info!("⚠️  Host process isolation test skipped due to insufficient privileges");
-            info!("   This test requires elevated privileges to create namespaces");
-            (true, true) // (success=true, skipped=true)
+            permission_error_outcome(ctx.userns_available, "host process isolation test")
         }
-        Ok(status) => {
-            error!("Host process isolation test failed with status: {}", status);
-            (false, false)
+        Ok(output) => {
+            error!("Host process isolation test failed with status: {}", output.status);
+            TestOutcome::fail().with_output(output.stdout, output.stderr)
         }
         Err(e) => {
             error!("Failed to run host process isolation test: {}", e);
-            (false, false)
+            TestOutcome::fail()
+        }
+    }
+}
+
+fn test_ro_bind_enforcement(ctx: &TestContext) -> TestOutcome {
+    // Create a file outside the sandbox, bind it in read-only, and verify writes to it
+    // fail with EROFS.
+    let target_path =
+        std::env::temp_dir().join(format!("sbx-helper-ro-bind-test-{}", std::process::id()));
+    if let Err(e) = std::fs::write(&target_path, b"original") {
+        error!("Failed to create ro-bind test file {}: {}", target_path.display(), e);
+        return TestOutcome::fail();
+    }
+    let target = target_path.to_string_lossy().to_string();
+
+    let test_result = helper_command(ctx)
+        .args(&["--ro-bind", &target, "../../target/debug/ro_bind_tester", "--path", &target])
+        .output();
+
+    let _ = std::fs::remove_file(&target_path);
+
+    match test_result {
+        Ok(output) if output.status.success() => {
+            info!("Write to --ro-bind path correctly failed with EROFS");
+            TestOutcome::pass().with_output(output.stdout, output.stderr)
+        }
+        Ok(output) if output.status.code() == Some(1) => {
+            permission_error_outcome(ctx.userns_available, "ro-bind enforcement test")
+        }
+        Ok(output) => {
+            error!("ro-bind enforcement test failed with status: {}", output.status);
+            TestOutcome::fail().with_output(output.stdout, output.stderr)
+        }
+        Err(e) => {
+            error!("Failed to run ro-bind enforcement test: {}", e);
+            TestOutcome::fail()
+        }
+    }
+}
+
+fn test_masked_path_enforcement(ctx: &TestContext) -> TestOutcome {
+    // Create a file with contents outside the sandbox, mask it, and verify it reads
+    // back empty from within the sandbox.
+    let target_path =
+        std::env::temp_dir().join(format!("sbx-helper-mask-path-test-{}", std::process::id()));
+    if let Err(e) = std::fs::write(&target_path, b"sensitive contents") {
+        error!("Failed to create mask-path test file {}: {}", target_path.display(), e);
+        return TestOutcome::fail();
+    }
+    let target = target_path.to_string_lossy().to_string();
+
+    let test_result = helper_command(ctx)
+        .args(&[
+            "--mask-path",
+            &target,
+            "../../target/debug/masked_path_tester",
+            "--path",
+            &target,
+        ])
+        .output();
+
+    let _ = std::fs::remove_file(&target_path);
+
+    match test_result {
+        Ok(output) if output.status.success() => {
+            info!("Masked path correctly read back empty");
+            TestOutcome::pass().with_output(output.stdout, output.stderr)
+        }
+        Ok(output) if output.status.code() == Some(1) => {
+            permission_error_outcome(ctx.userns_available, "masked-path enforcement test")
+        }
+        Ok(output) => {
+            error!("masked-path enforcement test failed with status: {}", output.status);
+            TestOutcome::fail().with_output(output.stdout, output.stderr)
+        }
+        Err(e) => {
+            error!("Failed to run masked-path enforcement test: {}", e);
+            TestOutcome::fail()
         }
     }
 }
+
+fn test_sysctl_enforcement(ctx: &TestContext) -> TestOutcome {
+    // kernel.msgmnb is virtualized per IPC namespace, so setting it inside the sandbox
+    // can't affect the host's value.
+    let key = "kernel.msgmnb";
+    let value = "32768";
+
+    let test_result = helper_command(ctx)
+        .args(&[
+            "--sysctl",
+            &format!("{}={}", key, value),
+            "../../target/debug/sysctl_tester",
+            "--key",
+            key,
+            "--expected",
+            value,
+        ])
+        .output();
+
+    match test_result {
+        Ok(output) if output.status.success() => {
+            info!("Sysctl {} was correctly observed as {} inside the sandbox", key, value);
+            TestOutcome::pass().with_output(output.stdout, output.stderr)
+        }
+        Ok(output) if output.status.code() == Some(1) => {
+            permission_error_outcome(ctx.userns_available, "sysctl enforcement test")
+        }
+        Ok(output) => {
+            error!("sysctl enforcement test failed with status: {}", output.status);
+            TestOutcome::fail().with_output(output.stdout, output.stderr)
+        }
+        Err(e) => {
+            error!("Failed to run sysctl enforcement test: {}", e);
+            TestOutcome::fail()
+        }
+    }
+}
+
+fn test_audit_observe_mode(ctx: &TestContext) -> TestOutcome {
+    // Run a trivial command under --audit and check the emitted report saw at least
+    // one syscall. Unlike the other tests, a missing report here means CAP_BPF/BTF
+    // support is unavailable in this environment rather than a missing user namespace,
+    // so we skip on an empty report instead of reusing `permission_error_outcome`.
+    let report_path = std::env::temp_dir()
+        .join(format!("sbx-helper-audit-test-{}-{}.json", std::process::id(), ctx.attempt));
+    let report = report_path.to_string_lossy().to_string();
+
+    let test_result = helper_command(ctx)
+        .args(&[
+            "--audit",
+            "--audit-output",
+            &report,
+            "/nix/store/xbp2j3z0lhizr5vvzff4dgdcxgs8i2w7-coreutils-9.7/bin/true",
+        ])
+        .output();
+
+    let outcome = match test_result {
+        Ok(output) if output.status.success() => {
+            match std::fs::read_to_string(&report).and_then(|s| {
+                serde_json::from_str::<serde_json::Value>(&s)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+            }) {
+                Ok(json) => {
+                    let syscall_count =
+                        json.get("syscalls").and_then(|v| v.as_array()).map(Vec::len).unwrap_or(0);
+                    if syscall_count > 0 {
+                        info!("Audit report recorded {} distinct syscalls", syscall_count);
+                        TestOutcome::pass()
+                    } else {
+                        info!("⚠️  audit observe mode: skipped (eBPF unavailable in this environment, empty report)");
+                        TestOutcome::skip()
+                    }
+                }
+                Err(e) => {
+                    info!(
+                        "⚠️  audit observe mode: skipped (no audit report written: {})",
+                        e
+                    );
+                    TestOutcome::skip()
+                }
+            }
+            .with_output(output.stdout, output.stderr)
+        }
+        Ok(output) if output.status.code() == Some(1) => {
+            permission_error_outcome(ctx.userns_available, "audit observe mode test")
+        }
+        Ok(output) => {
+            error!("audit observe mode test failed with status: {}", output.status);
+            TestOutcome::fail().with_output(output.stdout, output.stderr)
+        }
+        Err(e) => {
+            error!("Failed to run audit observe mode test: {}", e);
+            TestOutcome::fail()
+        }
+    };
+
+    let _ = std::fs::remove_file(&report_path);
+    outcome
+}
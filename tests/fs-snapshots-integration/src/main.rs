@@ -0,0 +1,189 @@
+//! Multi-provider filesystem snapshot integration test orchestrator
+//!
+//! This binary brings up one privileged, docker-compose-managed container per
+//! filesystem snapshot provider (ZFS, Btrfs, overlayfs), each provisioning its
+//! own loopback-backed pool/volume/mount, and runs the shared
+//! `filesystem_test_helpers`-based suite (`containerized_provider_tests` in
+//! `ah-fs-snapshots`) against it inside the container. Providers whose
+//! kernel/tooling/container prerequisites aren't available locally are
+//! skipped rather than failed, so contributors without ZFS (say) still get
+//! Btrfs/overlay coverage, while CI with the full prerequisite matrix gets
+//! every provider exercised.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::process::Command;
+use tracing::{error, info, warn};
+
+#[derive(Debug)]
+enum ProviderOutcome {
+    Passed,
+    Failed { output: String },
+    Skipped { reason: String },
+}
+
+#[derive(Debug)]
+struct ProviderResult {
+    provider: &'static str,
+    outcome: ProviderOutcome,
+}
+
+/// Check whether `docker compose` is usable (docker daemon reachable, compose
+/// plugin installed) before attempting to bring up any container.
+async fn compose_available() -> bool {
+    Command::new("docker")
+        .args(["compose", "version"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+/// Prerequisite check specific to a single provider, beyond `docker compose`
+/// itself being usable. Each provider needs a host kernel feature that a
+/// container alone can't fake: ZFS needs the host's `zfs` kernel module
+/// loaded, Btrfs needs `mkfs.btrfs`/loop device support, and overlayfs needs
+/// kernel overlay support (present on effectively every modern Linux, but
+/// checked anyway so the skip path is uniform).
+async fn provider_prerequisite(provider: &str) -> Result<(), String> {
+    match provider {
+        "zfs" => {
+            if Path::new("/sys/module/zfs").exists() {
+                Ok(())
+            } else {
+                Err("zfs kernel module not loaded on host".to_string())
+            }
+        }
+        "btrfs" => {
+            let has_mkfs = Command::new("which")
+                .arg("mkfs.btrfs")
+                .stdout(Stdio::null())
+                .status()
+                .await
+                .map(|s| s.success())
+                .unwrap_or(false);
+            if has_mkfs {
+                Ok(())
+            } else {
+                Err("mkfs.btrfs not found on host".to_string())
+            }
+        }
+        "overlay" => {
+            let filesystems = tokio::fs::read_to_string("/proc/filesystems").await.unwrap_or_default();
+            if filesystems.contains("overlay") {
+                Ok(())
+            } else {
+                Err("overlay filesystem not supported by host kernel".to_string())
+            }
+        }
+        other => Err(format!("unknown provider: {}", other)),
+    }
+}
+
+/// Run the `docker compose` service for a single provider and interpret the
+/// result.
+async fn run_provider(compose_file: &Path, provider: &'static str) -> ProviderResult {
+    if let Err(reason) = provider_prerequisite(provider).await {
+        warn!("Skipping provider '{}': {}", provider, reason);
+        return ProviderResult {
+            provider,
+            outcome: ProviderOutcome::Skipped { reason },
+        };
+    }
+
+    info!("Running containerized suite for provider '{}'", provider);
+
+    let output = Command::new("docker")
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("run")
+        .arg("--rm")
+        .arg(format!("{}-provider", provider))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .await;
+
+    match output {
+        Ok(output) if output.status.success() => {
+            info!("✓ Provider '{}' passed", provider);
+            ProviderResult {
+                provider,
+                outcome: ProviderOutcome::Passed,
+            }
+        }
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            error!("✗ Provider '{}' failed", provider);
+            ProviderResult {
+                provider,
+                outcome: ProviderOutcome::Failed {
+                    output: format!("stdout: {}\nstderr: {}", stdout, stderr),
+                },
+            }
+        }
+        Err(e) => {
+            error!("✗ Provider '{}' failed to execute: {}", provider, e);
+            ProviderResult {
+                provider,
+                outcome: ProviderOutcome::Failed {
+                    output: format!("Execution error: {}", e),
+                },
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    info!("Starting multi-provider filesystem snapshot integration tests");
+
+    let compose_file: PathBuf =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("docker-compose.yml");
+
+    if !compose_available().await {
+        warn!("docker compose is not available; skipping all providers");
+        println!("\n=== Filesystem Snapshot Provider Test Results ===");
+        for provider in ["zfs", "btrfs", "overlay"] {
+            println!("{}: SKIP (docker compose unavailable)", provider);
+        }
+        // Not a failure: this lets contributors without docker installed
+        // still run the rest of the test suite without CI signaling red.
+        return Ok(());
+    }
+
+    let mut results = Vec::new();
+    for provider in ["zfs", "btrfs", "overlay"] {
+        results.push(run_provider(&compose_file, provider).await);
+    }
+
+    println!("\n=== Filesystem Snapshot Provider Test Results ===");
+    let mut failed_count = 0;
+    for result in &results {
+        match &result.outcome {
+            ProviderOutcome::Passed => println!("{}: PASS", result.provider),
+            ProviderOutcome::Skipped { reason } => {
+                println!("{}: SKIP ({})", result.provider, reason)
+            }
+            ProviderOutcome::Failed { output } => {
+                failed_count += 1;
+                println!("{}: FAIL", result.provider);
+                println!("  Output: {}", output);
+            }
+        }
+    }
+
+    if failed_count == 0 {
+        info!("All available filesystem snapshot providers passed (or were skipped)!");
+        Ok(())
+    } else {
+        error!("{} provider(s) failed!", failed_count);
+        std::process::exit(1);
+    }
+}
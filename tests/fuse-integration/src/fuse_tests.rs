@@ -487,6 +487,110 @@ async fn test_large_directory_operations(test_fs: &TestFileSystem) -> Result<()>
     Ok(())
 }
 
+// ===== Case registry for the parallel/filtered test runner (see `crate::runner`) =====
+
+/// Mount a fresh filesystem, hand a [`TestFileSystem`] to `f`, then unmount.
+/// Each case gets its own mount so cases can run concurrently and a failure
+/// in one doesn't leave stale state for the next.
+async fn with_mounted_test_fs<F, Fut>(f: F) -> Result<()>
+where
+    F: FnOnce(TestFileSystem) -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let config = FuseTestConfig::default();
+    let mounted_fs = MountedFilesystem::mount(config).await?;
+    let test_fs = TestFileSystem::new(mounted_fs.mount_point.clone());
+    let result = f(test_fs).await;
+    mounted_fs.wait_for_unmount().await?;
+    result
+}
+
+macro_rules! case {
+    ($name:expr, $body:path) => {
+        crate::runner::TestCase {
+            name: $name,
+            run: || Box::pin($body()),
+        }
+    };
+}
+
+async fn case_mount_cycle() -> Result<()> {
+    run_mount_cycle_tests().await
+}
+
+async fn case_fs_file_operations() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_file_operations(&test_fs).await }).await
+}
+
+async fn case_fs_directory_operations() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_directory_operations(&test_fs).await }).await
+}
+
+async fn case_fs_permissions_and_attributes() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_permissions_and_attributes(&test_fs).await }).await
+}
+
+async fn case_fs_extended_attributes() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_extended_attributes(&test_fs).await }).await
+}
+
+async fn case_fs_large_file_operations() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_large_file_operations(&test_fs).await }).await
+}
+
+async fn case_control_plane_snapshot_operations() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_snapshot_operations(&test_fs).await }).await
+}
+
+async fn case_control_plane_branch_operations() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_branch_operations(&test_fs).await }).await
+}
+
+async fn case_control_plane_process_binding() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_process_binding(&test_fs).await }).await
+}
+
+async fn case_pjdfstest() -> Result<()> {
+    run_pjdfstest_compliance().await
+}
+
+async fn case_stress_concurrent_operations() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_concurrent_operations(&test_fs).await }).await
+}
+
+async fn case_stress_memory_pressure() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_memory_pressure(&test_fs).await }).await
+}
+
+async fn case_stress_large_directory_operations() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { test_large_directory_operations(&test_fs).await }).await
+}
+
+async fn case_stress_performance_benchmarks() -> Result<()> {
+    with_mounted_test_fs(|test_fs| async move { run_performance_benchmarks(&test_fs).await }).await
+}
+
+/// Every individually runnable test case across all suites, named
+/// `<suite>::<case>` for use with `--filter`.
+pub fn all_cases() -> Vec<crate::runner::TestCase> {
+    vec![
+        case!("mount_cycle::full", case_mount_cycle),
+        case!("fs_ops::file_operations", case_fs_file_operations),
+        case!("fs_ops::directory_operations", case_fs_directory_operations),
+        case!("fs_ops::permissions_and_attributes", case_fs_permissions_and_attributes),
+        case!("fs_ops::extended_attributes", case_fs_extended_attributes),
+        case!("fs_ops::large_file_operations", case_fs_large_file_operations),
+        case!("control_plane::snapshot_operations", case_control_plane_snapshot_operations),
+        case!("control_plane::branch_operations", case_control_plane_branch_operations),
+        case!("control_plane::process_binding", case_control_plane_process_binding),
+        case!("pjdfstest::compliance", case_pjdfstest),
+        case!("stress::concurrent_operations", case_stress_concurrent_operations),
+        case!("stress::memory_pressure", case_stress_memory_pressure),
+        case!("stress::large_directory_operations", case_stress_large_directory_operations),
+        case!("stress::performance_benchmarks", case_stress_performance_benchmarks),
+    ]
+}
+
 async fn run_performance_benchmarks(test_fs: &TestFileSystem) -> Result<()> {
     use std::time::Instant;
 
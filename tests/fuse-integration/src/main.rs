@@ -6,6 +6,7 @@
 
 #[cfg(feature = "fuse")]
 mod fuse_tests;
+mod runner;
 mod test_utils;
 
 #[cfg(feature = "fuse")]
@@ -30,6 +31,18 @@ struct Args {
     /// Test configuration file
     #[arg(short, long)]
     config: Option<PathBuf>,
+
+    /// Maximum number of test cases to run concurrently
+    #[arg(short, long, default_value = "10")]
+    jobs: usize,
+
+    /// Only run cases whose `suite::case` name matches this regex
+    #[arg(short, long)]
+    filter: Option<String>,
+
+    /// Re-run matching cases whenever a source file changes
+    #[arg(long)]
+    watch: bool,
 }
 
 #[derive(Subcommand)]
@@ -70,29 +83,80 @@ async fn main() -> Result<()> {
 
     info!(target: "fuse_integration_tests", "AgentFS FUSE Integration Test Runner starting");
 
-    match args.command {
-        Commands::All { skip_pjdfs, skip_stress } => {
-            run_all_tests(skip_pjdfs, skip_stress).await?;
-        }
-        Commands::MountCycle => {
-            run_mount_cycle_tests().await?;
-        }
-        Commands::FsOps => {
-            run_filesystem_ops_tests().await?;
-        }
-        Commands::ControlPlane => {
-            run_control_plane_tests().await?;
-        }
-        Commands::Pjdfstest => {
-            run_pjdfstest_compliance().await?;
+    #[cfg(feature = "fuse")]
+    {
+        let suite_prefix = match &args.command {
+            Commands::All { .. } => None,
+            Commands::MountCycle => Some("^mount_cycle::"),
+            Commands::FsOps => Some("^fs_ops::"),
+            Commands::ControlPlane => Some("^control_plane::"),
+            Commands::Pjdfstest => Some("^pjdfstest::"),
+            Commands::Stress => Some("^stress::"),
+        };
+
+        let (skip_pjdfs, skip_stress) = match &args.command {
+            Commands::All { skip_pjdfs, skip_stress } => (*skip_pjdfs, *skip_stress),
+            _ => (false, false),
+        };
+
+        let cases_fn = move || -> Vec<runner::TestCase> {
+            let mut cases = fuse_tests::all_cases();
+            if skip_pjdfs {
+                cases.retain(|case| !case.name.starts_with("pjdfstest::"));
+            }
+            if skip_stress {
+                cases.retain(|case| !case.name.starts_with("stress::"));
+            }
+            match suite_prefix {
+                Some(prefix) => runner::filter_cases(cases, Some(prefix)).expect("built-in suite regex is valid"),
+                None => cases,
+            }
+        };
+
+        if args.watch {
+            let watch_paths = vec![PathBuf::from("src")];
+            runner::watch(watch_paths, cases_fn, args.filter.clone(), args.jobs).await?;
+            return Ok(());
         }
-        Commands::Stress => {
-            run_stress_tests().await?;
+
+        let cases = runner::filter_cases(cases_fn(), args.filter.as_deref())?;
+        let results = runner::run_cases(cases, args.jobs).await;
+        let all_passed = runner::print_summary(&results);
+
+        if !all_passed {
+            anyhow::bail!("one or more test cases failed");
         }
+
+        info!(target: "fuse_integration_tests", "All tests completed successfully");
+        return Ok(());
     }
 
-    info!(target: "fuse_integration_tests", "All tests completed successfully");
-    Ok(())
+    #[cfg(not(feature = "fuse"))]
+    {
+        match args.command {
+            Commands::All { skip_pjdfs, skip_stress } => {
+                run_all_tests(skip_pjdfs, skip_stress).await?;
+            }
+            Commands::MountCycle => {
+                run_mount_cycle_tests().await?;
+            }
+            Commands::FsOps => {
+                run_filesystem_ops_tests().await?;
+            }
+            Commands::ControlPlane => {
+                run_control_plane_tests().await?;
+            }
+            Commands::Pjdfstest => {
+                run_pjdfstest_compliance().await?;
+            }
+            Commands::Stress => {
+                run_stress_tests().await?;
+            }
+        }
+
+        info!(target: "fuse_integration_tests", "All tests completed successfully");
+        Ok(())
+    }
 }
 
 async fn run_all_tests(skip_pjdfs: bool, skip_stress: bool) -> Result<()> {
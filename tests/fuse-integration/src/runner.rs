@@ -0,0 +1,170 @@
+//! Case-granular execution for the FUSE integration test suites.
+//!
+//! Unlike [`crate::fuse_tests::run_mount_cycle_tests`] and its siblings
+//! (which run a whole suite start-to-finish and bail on the first `?`),
+//! this module enumerates individual named test cases so a developer can
+//! select, parallelize, or re-run just the one that's failing.
+
+use anyhow::Result;
+use regex::Regex;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+/// Case names known to be flaky or currently disabled. Always skipped by
+/// [`run_cases`] and reported under their own "skip" bucket rather than
+/// silently omitted.
+pub const KNOWN_FLAKY: &[&str] = &["stress::memory_pressure"];
+
+type CaseFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// A single named, independently runnable test case.
+pub struct TestCase {
+    pub name: &'static str,
+    pub run: fn() -> CaseFuture,
+}
+
+/// Outcome of running one [`TestCase`].
+pub enum CaseStatus {
+    Pass,
+    Fail(String),
+    Skip(&'static str),
+}
+
+/// Result of running one case, including how long it took.
+pub struct CaseResult {
+    pub name: &'static str,
+    pub status: CaseStatus,
+    pub duration: Duration,
+}
+
+/// Select cases whose name matches `pattern` (a regex), or all cases if
+/// `pattern` is `None`.
+pub fn filter_cases(cases: Vec<TestCase>, pattern: Option<&str>) -> Result<Vec<TestCase>> {
+    let Some(pattern) = pattern else {
+        return Ok(cases);
+    };
+    let re = Regex::new(pattern)?;
+    Ok(cases.into_iter().filter(|case| re.is_match(case.name)).collect())
+}
+
+/// Run `cases` with at most `jobs` running concurrently, skipping any name
+/// in [`KNOWN_FLAKY`]. Returns one [`CaseResult`] per case, in the order
+/// the cases were given.
+pub async fn run_cases(cases: Vec<TestCase>, jobs: usize) -> Vec<CaseResult> {
+    let semaphore = Arc::new(Semaphore::new(jobs.max(1)));
+    let mut handles = Vec::with_capacity(cases.len());
+
+    for case in cases {
+        if let Some(reason) = KNOWN_FLAKY.iter().find(|&&flaky| flaky == case.name) {
+            let _ = reason;
+            handles.push(tokio::spawn(async move {
+                CaseResult {
+                    name: case.name,
+                    status: CaseStatus::Skip("known-flaky"),
+                    duration: Duration::ZERO,
+                }
+            }));
+            continue;
+        }
+
+        let semaphore = semaphore.clone();
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            let start = Instant::now();
+            let status = match (case.run)().await {
+                Ok(()) => CaseStatus::Pass,
+                Err(e) => CaseStatus::Fail(e.to_string()),
+            };
+            CaseResult { name: case.name, status, duration: start.elapsed() }
+        }));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        match handle.await {
+            Ok(result) => results.push(result),
+            Err(e) => results.push(CaseResult {
+                name: "<panicked case>",
+                status: CaseStatus::Fail(format!("test case panicked: {}", e)),
+                duration: Duration::ZERO,
+            }),
+        }
+    }
+    results
+}
+
+/// Print a one-line-per-case report followed by a pass/fail/skip summary.
+/// Returns `true` if every non-skipped case passed.
+pub fn print_summary(results: &[CaseResult]) -> bool {
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut skipped = 0;
+
+    for result in results {
+        match &result.status {
+            CaseStatus::Pass => {
+                passed += 1;
+                info!("✅ PASS {} ({:?})", result.name, result.duration);
+            }
+            CaseStatus::Fail(err) => {
+                failed += 1;
+                warn!("❌ FAIL {} ({:?}): {}", result.name, result.duration, err);
+            }
+            CaseStatus::Skip(reason) => {
+                skipped += 1;
+                info!("⏭️  SKIP {} ({})", result.name, reason);
+            }
+        }
+    }
+
+    info!("Summary: {} passed, {} failed, {} skipped, {} total", passed, failed, skipped, results.len());
+    failed == 0
+}
+
+/// Watch `watch_paths` for changes and re-run the cases from `all_cases()`
+/// that match `filter` each time a source file under them is modified,
+/// until interrupted with Ctrl-C.
+pub async fn watch(
+    watch_paths: Vec<std::path::PathBuf>,
+    all_cases: impl Fn() -> Vec<TestCase>,
+    filter: Option<String>,
+    jobs: usize,
+) -> Result<()> {
+    use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+    use tokio::sync::mpsc;
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })?;
+    for path in &watch_paths {
+        watcher.watch(path, RecursiveMode::Recursive)?;
+    }
+
+    info!("Watching {} path(s) for changes, press Ctrl-C to stop", watch_paths.len());
+
+    // Run once immediately, then again on every subsequent change event.
+    loop {
+        let cases = filter_cases(all_cases(), filter.as_deref())?;
+        let results = run_cases(cases, jobs).await;
+        print_summary(&results);
+
+        tokio::select! {
+            event = rx.recv() => {
+                match event {
+                    Some(Ok(_)) => info!("Change detected, re-running matching cases"),
+                    Some(Err(e)) => warn!("Watch error: {}", e),
+                    None => return Ok(()),
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                info!("Watch mode interrupted");
+                return Ok(());
+            }
+        }
+    }
+}